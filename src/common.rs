@@ -1,3 +1,6 @@
+use js_sys::{Function, Reflect};
+use wasm_bindgen::JsCast;
+use web_sys::{Element, KeyboardEvent, UrlSearchParams};
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
@@ -5,3 +8,46 @@ pub struct ComponentProps {
     #[prop_or_default]
     pub children: Children,
 }
+
+/// Whether a key press is meant for a form control rather than a shortcut.
+pub fn is_typing(e: &KeyboardEvent) -> bool {
+    e.target()
+        .and_then(|target| target.dyn_into::<Element>().ok())
+        .map(|element| matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT"))
+        .unwrap_or(false)
+}
+
+/// Reads a query parameter from the current page URL.
+pub fn url_param(name: &str) -> Option<String> {
+    let search = gloo_utils::window().location().search().ok()?;
+    UrlSearchParams::new_with_str(&search).ok()?.get(name)
+}
+
+/// Absolute link that opens `ticket` on `team`'s board.
+pub fn ticket_url(team: &str, ticket: &str) -> String {
+    let location = gloo_utils::window().location();
+    let params = UrlSearchParams::new().unwrap();
+    params.append("team", team);
+    params.append("ticket", ticket);
+    format!(
+        "{}{}?{}",
+        location.origin().unwrap_or_default(),
+        location.pathname().unwrap_or_default(),
+        String::from(params.to_string())
+    )
+}
+
+/// Writes `text` to the clipboard. `navigator.clipboard` is still an unstable
+/// API in web-sys, so it is called through reflection.
+pub fn copy_to_clipboard(text: &str) {
+    let navigator = gloo_utils::window().navigator();
+    let clipboard = match Reflect::get(&navigator, &"clipboard".into()) {
+        Ok(clipboard) => clipboard,
+        Err(_) => return,
+    };
+    if let Ok(write_text) = Reflect::get(&clipboard, &"writeText".into()) {
+        if let Some(write_text) = write_text.dyn_ref::<Function>() {
+            let _ = write_text.call1(&clipboard, &text.into());
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use super::card_edit_modal::{find_level, PRIORITIES, SEVERITIES, STATUSES};
+use crate::{common::ComponentProps, time::local_datetime, Activity, AppContext};
+use gloo_net::http::Request;
+use serde_json::Value;
+use stylist::yew::styled_component;
+use wasm_bindgen_futures::spawn_local;
+use yew::{
+    function_component, html, use_context, use_effect_with_deps, use_state, Html, Properties,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct ActivityLogProps {
+    pub ticket_id: String,
+}
+
+pub async fn fetch_activity(ticket_id: String) -> Vec<Activity> {
+    let response = Request::get(&format!("/api/tickets/{}/activity/", ticket_id))
+        .send()
+        .await;
+    match response {
+        Ok(r) => r.json::<Vec<Activity>>().await.unwrap_or_default(),
+        Err(_) => vec![],
+    }
+}
+
+/// Human readable form of a recorded field value, resolving ids to names.
+fn describe_value(context: &AppContext, field: &str, value: &Value) -> String {
+    match value {
+        Value::Null => String::from("none"),
+        Value::String(s) if s.is_empty() => String::from("none"),
+        Value::String(s) => match field {
+            "status" => STATUSES
+                .iter()
+                .find(|status| status.value == s)
+                .map(|status| status.label.to_string())
+                .unwrap_or_else(|| s.clone()),
+            "owner" | "assigned_to" | "reviewers" => context
+                .users
+                .iter()
+                .find(|user| &user.id == s)
+                .map(|user| user.name.clone())
+                .unwrap_or_else(|| s.clone()),
+            "priority" => find_level(PRIORITIES, &Some(s.clone()))
+                .map(|level| level.label.to_string())
+                .unwrap_or_else(|| s.clone()),
+            "severity" => find_level(SEVERITIES, &Some(s.clone()))
+                .map(|level| level.label.to_string())
+                .unwrap_or_else(|| s.clone()),
+            "labels" => context
+                .labels
+                .iter()
+                .find(|label| &label.id == s)
+                .map(|label| label.name.clone())
+                .unwrap_or_else(|| s.clone()),
+            _ => s.clone(),
+        },
+        Value::Array(values) if values.is_empty() => String::from("none"),
+        Value::Array(values) => values
+            .iter()
+            .map(|value| describe_value(context, field, value))
+            .collect::<Vec<String>>()
+            .join(", "),
+        // Users and labels may be recorded whole rather than by id.
+        Value::Object(object) => object
+            .get("name")
+            .and_then(Value::as_str)
+            .map(String::from)
+            .unwrap_or_else(|| value.to_string()),
+        _ => value.to_string(),
+    }
+}
+
+fn describe_action(activity: &Activity) -> String {
+    match activity.action.as_str() {
+        "created" => String::from("created the ticket"),
+        "deleted" => String::from("deleted the ticket"),
+        "status_changed" => String::from("changed the status"),
+        _ => match &activity.field {
+            Some(field) => format!("changed {}", field.replace('_', " ")),
+            None => activity.action.replace('_', " "),
+        },
+    }
+}
+
+#[styled_component(StyledActivityLog)]
+fn styled_activity_log(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            margin-top: 20px;
+            .activity-title {
+                font-size: 16px;
+                color: #808080;
+            }
+            .activity {
+                margin-top: 10px;
+                padding-left: 10px;
+                border-left: 2px solid #e0e0e0;
+                font-size: 13px;
+            }
+            .activity-actor {
+                font-weight: bold;
+                margin-right: 4px;
+            }
+            .activity-time {
+                font-size: 12px;
+                color: #808080;
+            }
+            .activity-change {
+                margin-top: 2px;
+                word-break: break-word;
+            }
+            .activity-before {
+                color: #f44336;
+                text-decoration: line-through;
+            }
+            .activity-after {
+                color: #2e7d32;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+#[function_component(ActivityLog)]
+pub fn activity_log(props: &ActivityLogProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let activity = use_state(Vec::<Activity>::new);
+
+    {
+        let activity = activity.clone();
+        // Refetched whenever the tickets change, so edits show up at once.
+        use_effect_with_deps(
+            move |(ticket_id, _)| {
+                let ticket_id = ticket_id.clone();
+                spawn_local(async move {
+                    activity.set(fetch_activity(ticket_id).await);
+                });
+                || ()
+            },
+            (props.ticket_id.clone(), context.revision),
+        );
+    }
+
+    html! {
+        <StyledActivityLog>
+            <div class="activity-title">{"Activity"}</div>
+            {if activity.is_empty() {
+                html!(<div class="activity">{"No activity recorded"}</div>)
+            } else {
+                activity.iter().map(|event| {
+                    let field = match (&event.field, event.action.as_str()) {
+                        (Some(field), _) => field.clone(),
+                        (None, "status_changed") => String::from("status"),
+                        (None, _) => String::from(""),
+                    };
+                    html! {
+                        <div class="activity">
+                            <div>
+                                <span class="activity-actor">
+                                    {match event.actor.clone() {
+                                        Some(actor) => actor.name,
+                                        None => String::from("Someone"),
+                                    }}
+                                </span>
+                                {describe_action(event)}
+                            </div>
+                            {if event.before.is_some() || event.after.is_some() {
+                                html! {
+                                    <div class="activity-change">
+                                        <span class="activity-before">
+                                            {describe_value(&context, &field, event.before.as_ref().unwrap_or(&Value::Null))}
+                                        </span>
+                                        {" → "}
+                                        <span class="activity-after">
+                                            {describe_value(&context, &field, event.after.as_ref().unwrap_or(&Value::Null))}
+                                        </span>
+                                    </div>
+                                }
+                            } else {
+                                html!()
+                            }}
+                            <div class="activity-time">
+                                {event.created_at.as_deref().and_then(local_datetime).unwrap_or_default()}
+                            </div>
+                        </div>
+                    }
+                }).collect::<Html>()
+            }}
+        </StyledActivityLog>
+    }
+}
@@ -0,0 +1,247 @@
+use super::{card_edit_modal::status_label, modal::Modal, ticket_search::SEARCH_DELAY_MS};
+use crate::{
+    common::ComponentProps, time::local_datetime, AppContext, OpenTicket, Ticket, TicketPage,
+};
+use gloo_net::http::Request;
+use gloo_timers::callback::Timeout;
+use stylist::yew::styled_component;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, InputEvent};
+use yew::{
+    function_component, html, use_context, use_effect_with_deps, use_state, Callback, Html,
+    Properties, TargetCast,
+};
+
+/// Archived tickets fetched per page.
+const ARCHIVE_PAGE_SIZE: usize = 25;
+
+#[derive(Properties, PartialEq)]
+pub struct ArchiveModalProps {
+    pub close: Callback<()>,
+}
+
+/// One page of `team`'s archived tickets matching `query`, newest first.
+async fn fetch_archive(team: String, query: String, cursor: Option<String>) -> Option<TicketPage> {
+    let mut request = Request::get("/api/tickets/").query([
+        ("team", team.as_str()),
+        ("archived", "true"),
+        ("limit", &ARCHIVE_PAGE_SIZE.to_string()),
+    ]);
+    if !query.trim().is_empty() {
+        request = request.query([("q", query.trim())]);
+    }
+    if let Some(cursor) = cursor {
+        request = request.query([("cursor", cursor.as_str())]);
+    }
+    match request.send().await {
+        Ok(r) => r.json::<TicketPage>().await.ok(),
+        Err(_) => None,
+    }
+}
+
+/// Archives a ticket, taking it off the board, or brings it back.
+pub async fn set_archived(ticket_id: String, archived: bool) {
+    let url = format!("/api/tickets/{}/archive", ticket_id);
+    let request = if archived {
+        Request::put(&url)
+    } else {
+        Request::delete(&url)
+    };
+    let _response = request.send().await;
+}
+
+#[styled_component(StyledArchiveModal)]
+fn styled_archive_modal(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            display: flex;
+            flex-direction: column;
+            width: 100%;
+            padding: 16px;
+            font-family: sans-serif;
+            font-size: 14px;
+            color: #4c4e52;
+            .archive-title {
+                font-size: 22px;
+            }
+            .archive-search {
+                margin-top: 12px;
+                padding: 6px;
+            }
+            .archive-empty {
+                margin-top: 20px;
+                color: #808080;
+            }
+            .archive-row {
+                display: flex;
+                align-items: center;
+                margin-top: 10px;
+            }
+            .archive-ticket {
+                display: flex;
+                flex-direction: column;
+                flex-grow: 1;
+                min-width: 0;
+            }
+            .archive-ticket > a {
+                overflow: hidden;
+                white-space: nowrap;
+                text-overflow: ellipsis;
+                color: #1976d2;
+                cursor: pointer;
+            }
+            .archive-ticket > span {
+                font-size: 12px;
+                color: #808080;
+            }
+            .archive-row > button {
+                margin-left: 5px;
+            }
+            .archive-more {
+                margin-top: 12px;
+                align-self: center;
+            }
+            button {
+                cursor: pointer;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+/// Searchable list of the current team's archived tickets.
+#[function_component(ArchiveModal)]
+pub fn archive_modal(props: &ArchiveModalProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let team = context.current_team.clone().unwrap_or_default();
+    let query = use_state(|| String::from(""));
+    // The query once typing has paused; only this one is sent.
+    let search = use_state(|| String::from(""));
+    // Loaded tickets and the next cursor, kept with the query they answer so
+    // a slow response for older input is never shown.
+    let results = use_state(|| None::<(String, Vec<Ticket>, Option<String>)>);
+
+    // Each keystroke restarts the timer; dropping the timeout cancels it.
+    {
+        let search = search.clone();
+        use_effect_with_deps(
+            move |query| {
+                let query = (**query).clone();
+                let timeout = Timeout::new(SEARCH_DELAY_MS, move || search.set(query));
+                move || drop(timeout)
+            },
+            query.clone(),
+        );
+    }
+    {
+        let results = results.clone();
+        use_effect_with_deps(
+            move |(team, query, _)| {
+                let team = team.clone();
+                let query = (**query).clone();
+                spawn_local(async move {
+                    if let Some(page) = fetch_archive(team, query.clone(), None).await {
+                        results.set(Some((query, page.tickets, page.next_cursor)));
+                    }
+                });
+                || ()
+            },
+            (team.clone(), search.clone(), context.revision),
+        );
+    }
+
+    let oninput = {
+        let query = query.clone();
+        Callback::from(move |e: InputEvent| {
+            query.set(e.target_dyn_into::<HtmlInputElement>().unwrap().value());
+        })
+    };
+    let load_more = {
+        let results = results.clone();
+        let team = team.clone();
+        Callback::from(move |_| {
+            let (query, tickets, cursor) = match (*results).clone() {
+                Some((query, tickets, Some(cursor))) => (query, tickets, cursor),
+                _ => return,
+            };
+            let results = results.clone();
+            let team = team.clone();
+            spawn_local(async move {
+                if let Some(page) = fetch_archive(team, query.clone(), Some(cursor)).await {
+                    let mut tickets = tickets;
+                    tickets.extend(page.tickets);
+                    results.set(Some((query, tickets, page.next_cursor)));
+                }
+            });
+        })
+    };
+    let open = |id: String| {
+        let update_open_ticket = context.update_open_ticket.clone();
+        let close = props.close.clone();
+        Callback::from(move |_| {
+            update_open_ticket.emit(Some(OpenTicket {
+                id: id.clone(),
+                field: None,
+            }));
+            close.emit(());
+        })
+    };
+    let restore = |id: String| {
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |_| {
+            let id = id.clone();
+            let update_tickets = update_tickets.clone();
+            spawn_local(async move {
+                set_archived(id, false).await;
+                update_tickets.emit(());
+            });
+        })
+    };
+
+    html! {
+        <Modal close={props.close.clone()}>
+            <StyledArchiveModal>
+                <div class="archive-title">{format!("{} Archive", team)}</div>
+                <input
+                    class="archive-search"
+                    type="text"
+                    placeholder="Search archived tickets"
+                    value={(*query).clone()}
+                    {oninput}
+                />
+                {match &*results {
+                    Some((for_query, tickets, cursor)) if *for_query == *query => html! {
+                        <>
+                            {if tickets.is_empty() {
+                                html!(<div class="archive-empty">{"No archived tickets found."}</div>)
+                            } else {
+                                tickets.iter().map(|ticket| {
+                                    let archived = ticket
+                                        .archived_at
+                                        .as_deref()
+                                        .and_then(local_datetime)
+                                        .map(|at| format!("{}, archived {}", status_label(&ticket.status), at))
+                                        .unwrap_or_else(|| status_label(&ticket.status).to_string());
+                                    html! {
+                                        <div class="archive-row" key={ticket.id.clone()}>
+                                            <div class="archive-ticket">
+                                                <a onclick={open(ticket.id.clone())}>{ticket.name()}</a>
+                                                <span>{archived}</span>
+                                            </div>
+                                            <button onclick={restore(ticket.id.clone())}>{"Restore"}</button>
+                                        </div>
+                                    }
+                                }).collect::<Html>()
+                            }}
+                            {if cursor.is_some() {
+                                html!(<button class="archive-more" onclick={load_more}>{"Load more"}</button>)
+                            } else {
+                                html!()
+                            }}
+                        </>
+                    },
+                    _ => html!(<div class="archive-empty">{"Loading..."}</div>),
+                }}
+            </StyledArchiveModal>
+        </Modal>
+    }
+}
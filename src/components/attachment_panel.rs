@@ -0,0 +1,342 @@
+use crate::{common::ComponentProps, time::local_datetime, AppContext, Attachment};
+use gloo_events::EventListener;
+use gloo_net::http::Request;
+use std::rc::Rc;
+use stylist::yew::styled_component;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{ClipboardEvent, DragEvent, Event, File, FileList, FormData, HtmlInputElement};
+use yew::{
+    classes, function_component, html, use_context, use_effect_with_deps, use_reducer, use_state,
+    Callback, Html, Properties, Reducible, TargetCast,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct AttachmentPanelProps {
+    pub ticket_id: String,
+}
+
+pub async fn fetch_attachments(ticket_id: String) -> Vec<Attachment> {
+    let response = Request::get(&format!("/api/tickets/{}/attachments/", ticket_id))
+        .send()
+        .await;
+    match response {
+        Ok(r) => r.json::<Vec<Attachment>>().await.unwrap_or_default(),
+        Err(_) => vec![],
+    }
+}
+
+/// Files still being uploaded, across every batch dropped or pasted.
+#[derive(Default, PartialEq)]
+struct Uploads {
+    pending: usize,
+}
+
+enum UploadsAction {
+    Add(usize),
+    Done(usize),
+}
+
+impl Reducible for Uploads {
+    type Action = UploadsAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let pending = match action {
+            UploadsAction::Add(count) => self.pending + count,
+            UploadsAction::Done(count) => self.pending.saturating_sub(count),
+        };
+        Rc::new(Uploads { pending })
+    }
+}
+
+async fn upload_attachment(ticket_id: String, file: File) {
+    let form = FormData::new().unwrap();
+    if form
+        .append_with_blob_and_filename("file", &file, &file.name())
+        .is_err()
+    {
+        return;
+    }
+    let _response = Request::post(&format!("/api/tickets/{}/attachments/", ticket_id))
+        .body(form)
+        .send()
+        .await;
+}
+
+async fn delete_attachment(id: String) {
+    let _response = Request::delete(&format!("/api/attachments/{}", id))
+        .send()
+        .await;
+}
+
+fn files(list: Option<FileList>) -> Vec<File> {
+    let list = match list {
+        Some(list) => list,
+        None => return vec![],
+    };
+    (0..list.length()).filter_map(|i| list.get(i)).collect()
+}
+
+/// File size in the largest unit that keeps it above one, e.g. "1.2 MB".
+fn format_size(size: u64) -> String {
+    let mut size = size as f64;
+    for unit in ["B", "KB", "MB"] {
+        if size < 1024.0 {
+            return match unit {
+                "B" => format!("{} {}", size, unit),
+                _ => format!("{:.1} {}", size, unit),
+            };
+        }
+        size /= 1024.0;
+    }
+    format!("{:.1} GB", size)
+}
+
+#[styled_component(StyledAttachmentPanel)]
+fn styled_attachment_panel(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            margin-top: 20px;
+            .attachments-title {
+                font-size: 16px;
+                color: #808080;
+            }
+            .attachments-drop {
+                margin-top: 8px;
+                padding: 12px;
+                border: 2px dashed #d3d3d3;
+                border-radius: 3px;
+                text-align: center;
+                font-size: 12px;
+                color: #808080;
+            }
+            .attachments-drop.dragging {
+                border-color: #74be43;
+                background-color: #f3faef;
+            }
+            .attachments-drop label {
+                color: #1976d2;
+                cursor: pointer;
+            }
+            .attachments-drop input {
+                display: none;
+            }
+            .attachment {
+                display: flex;
+                align-items: center;
+                margin-top: 8px;
+            }
+            .attachment-preview {
+                width: 48px;
+                height: 48px;
+                margin-right: 8px;
+                object-fit: cover;
+                border-radius: 3px;
+                background-color: #f0f0f0;
+            }
+            .attachment-detail {
+                display: flex;
+                flex-direction: column;
+                flex-grow: 1;
+                min-width: 0;
+            }
+            .attachment-detail > a {
+                overflow: hidden;
+                white-space: nowrap;
+                text-overflow: ellipsis;
+                color: #1976d2;
+            }
+            .attachment-meta {
+                font-size: 12px;
+                color: #808080;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+#[function_component(AttachmentPanel)]
+pub fn attachment_panel(props: &AttachmentPanelProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let attachments = use_state(Vec::<Attachment>::new);
+    let uploading = use_reducer(Uploads::default);
+    let dragging = use_state(|| false);
+
+    let reload = {
+        let ticket_id = props.ticket_id.clone();
+        let attachments = attachments.clone();
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |_| {
+            let ticket_id = ticket_id.clone();
+            let attachments = attachments.clone();
+            let update_tickets = update_tickets.clone();
+            spawn_local(async move {
+                attachments.set(fetch_attachments(ticket_id).await);
+                // Keeps the attachment count on the card in step.
+                update_tickets.emit(());
+            });
+        })
+    };
+    let upload = {
+        let ticket_id = props.ticket_id.clone();
+        let uploading = uploading.clone();
+        let reload = reload.clone();
+        Callback::from(move |files: Vec<File>| {
+            if files.is_empty() {
+                return;
+            }
+            let ticket_id = ticket_id.clone();
+            let uploading = uploading.clone();
+            let reload = reload.clone();
+            uploading.dispatch(UploadsAction::Add(files.len()));
+            spawn_local(async move {
+                for file in files {
+                    upload_attachment(ticket_id.clone(), file).await;
+                    uploading.dispatch(UploadsAction::Done(1));
+                }
+                reload.emit(());
+            });
+        })
+    };
+    {
+        let attachments = attachments.clone();
+        use_effect_with_deps(
+            move |ticket_id: &String| {
+                let ticket_id = ticket_id.clone();
+                spawn_local(async move {
+                    attachments.set(fetch_attachments(ticket_id).await);
+                });
+                || ()
+            },
+            props.ticket_id.clone(),
+        );
+    }
+    // Pasted images are uploaded while the ticket is open.
+    {
+        let upload = upload.clone();
+        use_effect_with_deps(
+            move |_| {
+                let listener = EventListener::new(&gloo_utils::document(), "paste", move |e| {
+                    let e = match e.dyn_ref::<ClipboardEvent>() {
+                        Some(e) => e,
+                        None => return,
+                    };
+                    let pasted = files(e.clipboard_data().and_then(|data| data.files()));
+                    if !pasted.is_empty() {
+                        e.prevent_default();
+                        upload.emit(pasted);
+                    }
+                });
+                move || drop(listener)
+            },
+            props.ticket_id.clone(),
+        );
+    }
+
+    let ondragover = {
+        let dragging = dragging.clone();
+        Callback::from(move |e: DragEvent| {
+            e.prevent_default();
+            dragging.set(true);
+        })
+    };
+    let ondragleave = {
+        let dragging = dragging.clone();
+        Callback::from(move |_: DragEvent| {
+            dragging.set(false);
+        })
+    };
+    let ondrop = {
+        let dragging = dragging.clone();
+        let upload = upload.clone();
+        Callback::from(move |e: DragEvent| {
+            e.prevent_default();
+            dragging.set(false);
+            upload.emit(files(e.data_transfer().and_then(|data| data.files())));
+        })
+    };
+    let onchange = {
+        let upload = upload.clone();
+        Callback::from(move |e: Event| {
+            let input = e.target_dyn_into::<HtmlInputElement>().unwrap();
+            upload.emit(files(input.files()));
+            input.set_value("");
+        })
+    };
+
+    html! {
+        <StyledAttachmentPanel>
+            <div class="attachments-title">
+                {format!("Attachments ({})", attachments.len())}
+            </div>
+            <div
+                class={classes!("attachments-drop", dragging.then_some("dragging"))}
+                {ondragover}
+                {ondragleave}
+                {ondrop}
+            >
+                {if uploading.pending > 0 {
+                    html!(<span>{format!("Uploading {} file(s)...", uploading.pending)}</span>)
+                } else {
+                    html! {
+                        <span>
+                            {"Drop files here, paste an image, or "}
+                            <label>
+                                {"browse"}
+                                <input type="file" multiple={true} {onchange} />
+                            </label>
+                        </span>
+                    }
+                }}
+            </div>
+            {attachments.iter().map(|attachment| {
+                let is_image = attachment.content_type.starts_with("image/");
+                let delete = {
+                    let id = attachment.id.clone();
+                    let reload = reload.clone();
+                    Callback::from(move |_| {
+                        let id = id.clone();
+                        let reload = reload.clone();
+                        spawn_local(async move {
+                            delete_attachment(id).await;
+                            reload.emit(());
+                        });
+                    })
+                };
+                html! {
+                    <div class="attachment" key={attachment.id.clone()}>
+                        {if is_image {
+                            html! {
+                                <a href={attachment.url.clone()} target="_blank" rel="noopener noreferrer">
+                                    <img
+                                        class="attachment-preview"
+                                        src={attachment.thumbnail_url.clone().unwrap_or(attachment.url.clone())}
+                                        alt={attachment.filename.clone()}
+                                    />
+                                </a>
+                            }
+                        } else {
+                            html!()
+                        }}
+                        <div class="attachment-detail">
+                            <a href={attachment.url.clone()} download={attachment.filename.clone()}>
+                                {attachment.filename.clone()}
+                            </a>
+                            <span class="attachment-meta">
+                                {format!(
+                                    "{} · {} · {}",
+                                    format_size(attachment.size),
+                                    match attachment.uploaded_by.clone() {
+                                        Some(user) => user.name,
+                                        None => String::from("Unknown"),
+                                    },
+                                    attachment.created_at.as_deref().and_then(local_datetime).unwrap_or_default()
+                                )}
+                            </span>
+                        </div>
+                        <button onclick={delete}>{"Delete"}</button>
+                    </div>
+                }
+            }).collect::<Html>()}
+        </StyledAttachmentPanel>
+    }
+}
@@ -1,14 +1,113 @@
-use super::{card::Card, card_create_modal::CardCreateModal};
-use crate::{common::ComponentProps, Ticket};
+use super::{
+    card::{self, Card},
+    card_edit_modal::PRIORITIES,
+};
+use crate::{common::ComponentProps, AppContext, Ticket};
+use std::{cmp::Ordering, rc::Rc};
 use stylist::{css, yew::styled_component};
-use yew::{function_component, html, use_state, Callback, Html, Properties};
+use web_sys::{Element, Event, HtmlSelectElement};
+use yew::{
+    function_component, html, use_context, use_effect, use_effect_with_deps, use_node_ref,
+    use_state_eq, Callback, Html, Properties, TargetCast,
+};
 use yew_icons::{Icon, IconId};
 
 #[derive(Properties, PartialEq)]
 pub struct BoardProps {
     pub title: String,
     pub status: String,
-    pub tickets: Vec<Ticket>,
+    pub sort: String,
+    pub tickets: Vec<Rc<Ticket>>,
+    pub total: usize,
+    pub has_more: bool,
+    pub loading: bool,
+    /// Index of the keyboard-focused card in `tickets`.
+    #[prop_or_default]
+    pub focused: Option<usize>,
+    pub open_create_modal: Callback<()>,
+}
+
+#[derive(Clone)]
+pub struct Sort {
+    pub value: &'static str,
+    pub label: &'static str,
+}
+
+/// Height of a card plus its top margin. Cards have a fixed height so the
+/// visible window of a column can be worked out from the scroll offset alone.
+const CARD_HEIGHT: i32 = card::CARD_HEIGHT + card::CARD_MARGIN;
+
+/// Number of extra cards mounted above and below the visible window.
+const OVERSCAN: usize = 4;
+
+/// Distance from the bottom of a column at which the next page is requested.
+const LOAD_MORE_THRESHOLD: i32 = 4 * CARD_HEIGHT;
+
+pub const DEFAULT_SORT: &str = "newest";
+
+pub const SORTS: &[Sort] = &[
+    Sort {
+        value: "newest",
+        label: "Newest",
+    },
+    Sort {
+        value: "oldest",
+        label: "Oldest",
+    },
+    Sort {
+        value: "updated",
+        label: "Recently Updated",
+    },
+    // Kept as "assignee" so sorts saved before owners and reviewers still apply.
+    Sort {
+        value: "assignee",
+        label: "Owner",
+    },
+    Sort {
+        value: "priority",
+        label: "Priority",
+    },
+    Sort {
+        value: "title",
+        label: "Title",
+    },
+];
+
+fn owner_name(ticket: &Ticket) -> Option<String> {
+    ticket.owner().map(|u| u.name.to_lowercase())
+}
+
+/// Puts set values first, in ascending order, and unset values last.
+fn cmp_set_first<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn priority_rank(ticket: &Ticket) -> Option<usize> {
+    let priority = ticket.priority.as_ref()?;
+    PRIORITIES.iter().position(|p| p.value == priority.as_str())
+}
+
+/// Sorts tickets in place by `sort`, falling back to newest first and then
+/// title so tickets that tie on the primary key keep a stable order.
+pub fn sort_tickets(tickets: &mut [Rc<Ticket>], sort: &str) {
+    tickets.sort_by(|a, b| {
+        let primary = match sort {
+            "oldest" => a.created_at.cmp(&b.created_at),
+            "updated" => b.updated_at.cmp(&a.updated_at),
+            "assignee" => cmp_set_first(owner_name(a), owner_name(b)),
+            "priority" => cmp_set_first(priority_rank(a), priority_rank(b)),
+            "title" => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+            _ => b.created_at.cmp(&a.created_at),
+        };
+        primary
+            .then_with(|| b.created_at.cmp(&a.created_at))
+            .then_with(|| a.title.to_lowercase().cmp(&b.title.to_lowercase()))
+    });
 }
 
 #[styled_component(StyledBoard)]
@@ -34,36 +133,134 @@ fn styled_board(props: &ComponentProps) -> Html {
                 font-size: 18px;
                 color: #808080;
             }
+            .board-count {
+                margin-left: 6px;
+                font-size: 14px;
+            }
+            .board-sort {
+                margin-left: auto;
+                margin-right: 8px;
+                border: none;
+                background-color: transparent;
+                color: #808080;
+                cursor: pointer;
+            }
             .card-container {
                 height: 100%;
                 width: 100%;
                 border-radius: 10px;
                 overflow: auto;
             }
+            .card-container-footer {
+                display: flex;
+                justify-content: center;
+                margin: 16px 0;
+                font-family: sans-serif;
+                font-size: 14px;
+                color: #808080;
+            }
         ")}>{props.children.clone()}</div>
     }
 }
 
 #[function_component(Board)]
 pub fn board(props: &BoardProps) -> Html {
-    let show_create_modal = use_state(|| false);
+    let context = use_context::<AppContext>().expect("no ctx found");
     let open_create_modal = {
-        let show_create_modal = show_create_modal.clone();
+        let open_create_modal = props.open_create_modal.clone();
         Callback::from(move |_| {
-            show_create_modal.set(true);
+            open_create_modal.emit(());
         })
     };
-    let close_edit_modal = {
-        let show_create_modal = show_create_modal.clone();
+
+    let container_ref = use_node_ref();
+    let scroll_top = use_state_eq(|| 0);
+    let viewport_height = use_state_eq(|| 0);
+    {
+        let container_ref = container_ref.clone();
+        let viewport_height = viewport_height.clone();
+        use_effect(move || {
+            if let Some(container) = container_ref.cast::<Element>() {
+                viewport_height.set(container.client_height());
+            }
+            || ()
+        });
+    }
+    {
+        let container_ref = container_ref.clone();
+        use_effect_with_deps(
+            move |focused| {
+                if let (Some(row), Some(container)) = (*focused, container_ref.cast::<Element>()) {
+                    let top = row as i32 * CARD_HEIGHT;
+                    let bottom = top + CARD_HEIGHT - container.client_height();
+                    if top < container.scroll_top() {
+                        container.set_scroll_top(top);
+                    } else if bottom > container.scroll_top() {
+                        container.set_scroll_top(bottom);
+                    }
+                }
+                || ()
+            },
+            props.focused,
+        );
+    }
+    let load_more = {
+        let status = props.status.clone();
+        let load_more = context.load_more.clone();
         Callback::from(move |_| {
-            show_create_modal.set(false);
+            load_more.emit(status.clone());
+        })
+    };
+    let update_scroll = {
+        let scroll_top = scroll_top.clone();
+        let viewport_height = viewport_height.clone();
+        let load_more = load_more.clone();
+        let has_more = props.has_more;
+        Callback::from(move |e: Event| {
+            if let Some(target) = e.target_dyn_into::<Element>() {
+                scroll_top.set(target.scroll_top());
+                viewport_height.set(target.client_height());
+                let remaining =
+                    target.scroll_height() - target.scroll_top() - target.client_height();
+                if has_more && remaining < LOAD_MORE_THRESHOLD {
+                    load_more.emit(());
+                }
+            }
+        })
+    };
+    let first = (*scroll_top / CARD_HEIGHT) as usize;
+    let first = first.saturating_sub(OVERSCAN).min(props.tickets.len());
+    let last = (first + (*viewport_height / CARD_HEIGHT) as usize + 2 * OVERSCAN + 1)
+        .min(props.tickets.len());
+
+    let update_sort = {
+        let status = props.status.clone();
+        let update_sort = context.update_sort.clone();
+        Callback::from(move |e: Event| {
+            update_sort.emit((
+                status.clone(),
+                e.target_dyn_into::<HtmlSelectElement>().unwrap().value(),
+            ));
         })
     };
 
     html! {
         <StyledBoard>
             <div class="board-header">
-                <div class="board-title">{props.title.clone()}</div>
+                <div class="board-title">
+                    {props.title.clone()}
+                    <span class="board-count">{format!("({})", props.total)}</span>
+                </div>
+                <select class="board-sort" onchange={update_sort}>
+                    {SORTS.iter().map(|sort| {
+                        html! {
+                            <option
+                                value={sort.value}
+                                selected={sort.value == props.sort}
+                            >{sort.label}</option>
+                        }
+                    }).collect::<Html>()}
+                </select>
                 <span onclick={open_create_modal}>
                     <Icon
                         icon_id={IconId::FontAwesomeSolidCirclePlus}
@@ -71,18 +268,93 @@ pub fn board(props: &BoardProps) -> Html {
                     />
                 </span>
             </div>
-            <div class="card-container">
-                {props.tickets.clone().into_iter().map(|ticket| {
-                    html!{<Card ticket={ticket} />}
+            <div class="card-container" ref={container_ref} onscroll={update_scroll}>
+                <div style={format!("height: {}px;", first as i32 * CARD_HEIGHT)} />
+                {props.tickets[first..last].iter().enumerate().map(|(i, ticket)| {
+                    html!{
+                        <Card
+                            key={ticket.id.clone()}
+                            ticket={ticket.clone()}
+                            focused={props.focused == Some(first + i)}
+                        />
+                    }
                 }).collect::<Html>()}
+                <div style={format!(
+                    "height: {}px;",
+                    (props.tickets.len() - last) as i32 * CARD_HEIGHT
+                )} />
+                {if props.loading {
+                    html! {<div class="card-container-footer">{"Loading..."}</div>}
+                } else if props.has_more {
+                    html! {
+                        <div class="card-container-footer">
+                            <button onclick={load_more.reform(|_| ())}>
+                                {format!("Load more ({} of {})", props.tickets.len(), props.total)}
+                            </button>
+                        </div>
+                    }
+                } else {
+                    html!()
+                }}
             </div>
-            {if *show_create_modal {
-                html! {
-                    <CardCreateModal status={props.status.clone()} close={close_edit_modal} />
-                }
-            } else {
-                html!()
-            }}
         </StyledBoard>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn ticket(title: &str, created_at: &str, owner: Option<&str>) -> Rc<Ticket> {
+        let owner = owner.map(|name| json!({"id": name, "name": name}));
+        Rc::new(
+            serde_json::from_value(json!({
+                "id": title,
+                "title": title,
+                "description": "",
+                "status": "Open",
+                "assigned_to": owner,
+                "created_at": created_at,
+            }))
+            .unwrap(),
+        )
+    }
+
+    fn titles(tickets: &[Rc<Ticket>]) -> Vec<&str> {
+        tickets.iter().map(|t| t.title.as_str()).collect()
+    }
+
+    #[test]
+    fn puts_unassigned_tickets_last() {
+        let mut tickets = vec![
+            ticket("a", "2024-01-01", None),
+            ticket("b", "2024-01-02", Some("Zoe")),
+            ticket("c", "2024-01-03", Some("amy")),
+        ];
+        sort_tickets(&mut tickets, "assignee");
+        assert_eq!(titles(&tickets), ["c", "b", "a"]);
+    }
+
+    #[test]
+    fn breaks_ties_by_newest_then_title() {
+        let mut tickets = vec![
+            ticket("b", "2024-01-01", Some("amy")),
+            ticket("c", "2024-01-02", Some("Amy")),
+            ticket("a", "2024-01-01", Some("amy")),
+        ];
+        sort_tickets(&mut tickets, "assignee");
+        assert_eq!(titles(&tickets), ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn falls_back_to_newest_first() {
+        let mut tickets = vec![
+            ticket("a", "2024-01-01", None),
+            ticket("b", "2024-01-03", None),
+            ticket("c", "2024-01-02", None),
+        ];
+        sort_tickets(&mut tickets, "unknown");
+        assert_eq!(titles(&tickets), ["b", "c", "a"]);
+    }
+}
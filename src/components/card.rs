@@ -1,21 +1,52 @@
-use super::card_edit_modal::CardEditModal;
-use crate::{common::ComponentProps, Ticket};
+use super::{
+    card_edit_modal::{find_level, PRIORITIES, SEVERITIES},
+    card_menu::CardMenu,
+    checklist::checklist_progress,
+    label_picker::label_chip,
+    subtasks::subtask_progress,
+    ticket_links::open_blockers,
+    user_picker::avatar,
+};
+use crate::{
+    time::{age, due_badge, local_datetime},
+    AppContext, OpenTicket, Ticket,
+};
+use std::rc::Rc;
 use stylist::{css, yew::styled_component};
-use yew::{function_component, html, use_state, Callback, Properties};
+use yew::{classes, function_component, html, use_context, Callback, Children, Html, Properties};
+use yew_icons::{Icon, IconId};
+
+/// Fixed height of a card, which the board's windowing relies on.
+pub const CARD_HEIGHT: i32 = 104;
+pub const CARD_MARGIN: i32 = 16;
+
+/// Label chips shown on a card before the rest collapse into "+N".
+const MAX_CARD_LABELS: usize = 2;
 
 #[derive(Properties, PartialEq)]
 pub struct CardProps {
-    pub ticket: Ticket,
+    pub ticket: Rc<Ticket>,
+    #[prop_or_default]
+    pub focused: bool,
+}
+
+#[derive(Properties, PartialEq)]
+struct StyledCardProps {
+    #[prop_or_default]
+    children: Children,
+    focused: bool,
+    /// Colour of the left edge, taken from the ticket's severity.
+    accent: Option<&'static str>,
 }
 
 #[styled_component(StyledCard)]
-fn styled_card(props: &ComponentProps) -> Html {
+fn styled_card(props: &StyledCardProps) -> Html {
     html! {
-        <div class={css!("
-            min-height: 80px;
+        <div class={classes!(css!("
+            height: ${height}px;
             box-shadow: 0 10px 15px -3px rgb(0 0 0 / 0.1),
               0 4px 6px -4px rgb(0 0 0 / 0.1);
-            margin-top: 16px;
+            margin-top: ${margin}px;
             cursor: pointer;
             background-color: #fff;
             border-radius: 3px;
@@ -25,7 +56,13 @@ fn styled_card(props: &ComponentProps) -> Html {
                 box-shadow: 0 20px 25px -5px rgb(0 0 0 / 0.1),
                 0 8px 10px -6px rgb(0 0 0 / 0.1);
             }
+            &.card-focused {
+                outline: 2px solid #74be43;
+                outline-offset: -2px;
+            }
             .card-detail {
+                flex-grow: 1;
+                min-width: 0;
                 padding: 10px;
                 font-family: sans-serif;
                 font-size: 14px;
@@ -34,6 +71,95 @@ fn styled_card(props: &ComponentProps) -> Html {
                 justify-content: space-between;
                 color: #4c4e52;
             }
+            .card-detail > div {
+                overflow: hidden;
+                white-space: nowrap;
+                text-overflow: ellipsis;
+            }
+            .card-priority {
+                color: #fff;
+                font-size: 11px;
+                font-weight: bold;
+                border-radius: 3px;
+                padding: 1px 4px;
+                margin-right: 6px;
+            }
+            .card-blocked {
+                color: #fff;
+                background-color: #f44336;
+                font-size: 11px;
+                font-weight: bold;
+                border-radius: 3px;
+                padding: 1px 4px;
+                margin-right: 6px;
+            }
+            .card-key {
+                color: #808080;
+                margin-right: 6px;
+            }
+            .card-footer {
+                display: flex;
+                flex-wrap: wrap;
+                align-items: center;
+                gap: 2px 4px;
+                max-height: 44px;
+            }
+            .card-avatars {
+                display: flex;
+                margin-right: 4px;
+            }
+            .card-avatars > .avatar {
+                border: 2px solid #fff;
+            }
+            .card-avatars > .avatar + .avatar {
+                margin-left: -6px;
+            }
+            .card-unassigned {
+                margin-right: 4px;
+            }
+            .card-due {
+                color: #fff;
+                font-size: 10px;
+                font-weight: bold;
+                border-radius: 3px;
+                padding: 1px 4px;
+            }
+            .card-comments, .card-attachments {
+                display: flex;
+                align-items: center;
+                gap: 2px;
+                font-size: 11px;
+                color: #808080;
+            }
+            .card-checklist {
+                font-size: 11px;
+                color: #808080;
+            }
+            .card-checklist.done {
+                color: #2e7d32;
+            }
+            .card-subtasks {
+                font-size: 11px;
+                color: #808080;
+            }
+            .card-subtasks.done {
+                color: #2e7d32;
+            }
+            .card-age {
+                margin-left: auto;
+                font-size: 11px;
+                color: #808080;
+            }
+            .label-chip {
+                color: #fff;
+                font-size: 10px;
+                border-radius: 8px;
+                padding: 1px 6px;
+            }
+            .card-more-labels {
+                font-size: 10px;
+                color: #808080;
+            }
             .card-ctrls {
                 display: flex;
                 flex-direction: column;
@@ -41,45 +167,159 @@ fn styled_card(props: &ComponentProps) -> Html {
                 align-items: center;
                 padding: 0 10px;
             }
-        ")}>{props.children.clone()}</div>
+        ", height = CARD_HEIGHT, margin = CARD_MARGIN), props.focused.then_some("card-focused"))}
+            style={props.accent.map(|color| format!("border-left: 4px solid {};", color))}
+        >{props.children.clone()}</div>
     }
 }
 
 #[function_component(Card)]
 pub fn card(props: &CardProps) -> Html {
-    let show_edit_modal = use_state(|| false);
+    let context = use_context::<AppContext>().expect("no ctx found");
     let open_edit_modal = {
-        let show_edit_modal = show_edit_modal.clone();
+        let id = props.ticket.id.clone();
+        let update_open_ticket = context.update_open_ticket.clone();
         Callback::from(move |_| {
-            show_edit_modal.set(true);
-        })
-    };
-    let close_edit_modal = {
-        let show_edit_modal = show_edit_modal.clone();
-        Callback::from(move |_| {
-            show_edit_modal.set(false);
+            update_open_ticket.emit(Some(OpenTicket {
+                id: id.clone(),
+                field: None,
+            }));
         })
     };
 
+    let blockers = open_blockers(&props.ticket);
+
     html! {
-        <StyledCard>
+        <StyledCard
+            focused={props.focused}
+            accent={find_level(SEVERITIES, &props.ticket.severity).map(|s| s.color)}
+        >
             <div class="card-detail" onclick={open_edit_modal}>
-                <div class="card-title">{props.ticket.title.clone()}</div>
+                <div class="card-title">
+                    {if blockers.is_empty() {
+                        html!()
+                    } else {
+                        html! {
+                            <span
+                                class="card-blocked"
+                                title={blockers
+                                    .iter()
+                                    .map(|link| format!("Blocked by {}", link.ticket.name()))
+                                    .collect::<Vec<String>>()
+                                    .join("\n")}
+                            >{"Blocked"}</span>
+                        }
+                    }}
+                    {match find_level(PRIORITIES, &props.ticket.priority) {
+                        Some(priority) => html! {
+                            <span
+                                class="card-priority"
+                                style={format!("background-color: {};", priority.color)}
+                            >{priority.value}</span>
+                        },
+                        None => html!(),
+                    }}
+                    {match &props.ticket.key {
+                        Some(key) => html!(<span class="card-key">{key.clone()}</span>),
+                        None => html!(),
+                    }}
+                    {props.ticket.title.clone()}
+                </div>
                 <div class="card-description">{props.ticket.description.clone()}</div>
-                <div class="card-assigned-to">
-                    {match props.ticket.assigned_to.clone() {
-                        Some(u) => u.name,
-                        None => "Unassigned".into(),
+                <div class="card-footer">
+                    {if props.ticket.owner().is_none() && props.ticket.reviewers.is_empty() {
+                        html!(<span class="card-unassigned">{"Unassigned"}</span>)
+                    } else {
+                        html! {
+                            <span class="card-avatars">
+                                {props.ticket.owner().iter().map(|owner| avatar(owner, "owner")).collect::<Html>()}
+                                {props.ticket.reviewers.iter().map(|reviewer| avatar(reviewer, "reviewer")).collect::<Html>()}
+                            </span>
+                        }
+                    }}
+                    {match props.ticket.due_date.as_deref().and_then(due_badge) {
+                        Some((badge, color)) => html! {
+                            <span
+                                class="card-due"
+                                style={format!("background-color: {};", color)}
+                            >{badge}</span>
+                        },
+                        None => html!(),
+                    }}
+                    {props.ticket.labels.iter().take(MAX_CARD_LABELS).map(label_chip).collect::<Html>()}
+                    {if props.ticket.labels.len() > MAX_CARD_LABELS {
+                        html! {
+                            <span
+                                class="card-more-labels"
+                                title={props.ticket.labels[MAX_CARD_LABELS..]
+                                    .iter()
+                                    .map(|label| label.name.clone())
+                                    .collect::<Vec<String>>()
+                                    .join(", ")}
+                            >{format!("+{}", props.ticket.labels.len() - MAX_CARD_LABELS)}</span>
+                        }
+                    } else {
+                        html!()
+                    }}
+                    {match checklist_progress(&props.ticket) {
+                        (_, 0) => html!(),
+                        (done, total) => html! {
+                            <span class={classes!("card-checklist", (done == total).then_some("done"))}>
+                                {format!("☑ {}/{}", done, total)}
+                            </span>
+                        },
+                    }}
+                    {match subtask_progress(&props.ticket) {
+                        (_, 0) => html!(),
+                        (done, total) => html! {
+                            <span
+                                class={classes!("card-subtasks", (done == total).then_some("done"))}
+                                title={format!("{}/{} sub-tasks accepted", done, total)}
+                            >
+                                {format!("⧉ {}/{}", done, total)}
+                            </span>
+                        },
+                    }}
+                    {if props.ticket.comment_count > 0 {
+                        html! {
+                            <span class="card-comments">
+                                <Icon
+                                    icon_id={IconId::FontAwesomeSolidComment}
+                                    style={"height: 10px; width: 10px; fill: #808080;"}
+                                />
+                                {props.ticket.comment_count}
+                            </span>
+                        }
+                    } else {
+                        html!()
+                    }}
+                    {if props.ticket.attachment_count > 0 {
+                        html! {
+                            <span class="card-attachments">
+                                <Icon
+                                    icon_id={IconId::FontAwesomeSolidPaperclip}
+                                    style={"height: 10px; width: 10px; fill: #808080;"}
+                                />
+                                {props.ticket.attachment_count}
+                            </span>
+                        }
+                    } else {
+                        html!()
+                    }}
+                    {match props.ticket.created_at.as_deref().and_then(age) {
+                        Some(age) => html! {
+                            <span
+                                class="card-age"
+                                title={props.ticket.created_at.as_deref().and_then(local_datetime)}
+                            >{age}</span>
+                        },
+                        None => html!(),
                     }}
                 </div>
             </div>
-            {if *show_edit_modal {
-                html! {
-                    <CardEditModal ticket={props.ticket.clone()} close={close_edit_modal} />
-                }
-            } else {
-                html!()
-            }}
+            <div class="card-ctrls">
+                <CardMenu ticket={props.ticket.clone()} />
+            </div>
         </StyledCard>
     }
 }
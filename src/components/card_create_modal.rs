@@ -1,10 +1,20 @@
-use super::modal::Modal;
-use crate::{common::ComponentProps, AppContext};
+use super::{
+    card_edit_modal::{PRIORITIES, SEVERITIES, STATUSES},
+    checklist::completion_blocker,
+    custom_fields::{form_fields, CustomFieldInput, FormField},
+    label_picker::LabelPicker,
+    markdown_editor::MarkdownEditor,
+    modal::Modal,
+    templates::expand_title,
+    user_picker::UserPicker,
+};
+use crate::{common::ComponentProps, AppContext, CustomField, Template};
 use gloo_net::http::Request;
-use serde_json::json;
+use serde_json::{json, Value};
+use std::collections::HashMap;
 use stylist::yew::styled_component;
 use wasm_bindgen_futures::spawn_local;
-use web_sys::{Event, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+use web_sys::{Event, HtmlInputElement, HtmlSelectElement};
 use yew::{
     function_component, html, use_context, use_state, Callback, Html, Properties, TargetCast,
 };
@@ -15,21 +25,40 @@ pub struct CardCreateModalProps {
     pub close: Callback<()>,
 }
 
+#[derive(Clone, Default)]
 pub struct NewTicket {
     pub title: String,
     pub description: String,
     pub status: String,
-    pub assigned_to: String,
+    pub owner: String,
+    pub reviewers: Vec<String>,
+    pub priority: String,
+    pub severity: String,
+    pub labels: Vec<String>,
+    pub custom_fields: HashMap<String, Value>,
+    pub due_date: String,
+    /// Checklist item texts, usually filled in from a template.
+    pub checklist: Vec<String>,
+    /// Id of the epic the new ticket is a sub-task of, if any.
+    pub parent_id: String,
     pub team_id: String,
 }
 
-async fn create_ticket(ticket: &NewTicket) {
+pub async fn create_ticket(ticket: &NewTicket) {
     let _response = Request::post("/api/tickets/")
         .json(&json!({
             "title": ticket.title,
             "description": ticket.description,
             "status": ticket.status,
-            "assigned_to": ticket.assigned_to,
+            "owner": ticket.owner,
+            "reviewers": ticket.reviewers,
+            "priority": ticket.priority,
+            "severity": ticket.severity,
+            "labels": ticket.labels,
+            "custom_fields": ticket.custom_fields,
+            "due_date": ticket.due_date,
+            "checklist": ticket.checklist,
+            "parent_id": ticket.parent_id,
             "team_id": ticket.team_id,
         }))
         .unwrap()
@@ -61,17 +90,20 @@ pub fn styled_card_create_modal(props: &ComponentProps) -> Html {
             .new-ticket-input-group > input {
                 width: 100%;
             }
-            .new-ticket-input-group > textarea {
-                width: 100%;
-                height: 65px;
-                font-family: sans-serif;
-                resize: none;
+            .new-ticket-checklist {
+                margin: 0;
+                padding-left: 20px;
             }
             .new-ticket-action-group {
                 display: flex;
                 justify-content: end;
+                align-items: center;
                 margin-top: 20px;
             }
+            .new-ticket-error {
+                font-size: 12px;
+                color: #f44336;
+            }
             .new-ticket-action-group > button {
                 margin-left: 5px;
             }
@@ -82,43 +114,75 @@ pub fn styled_card_create_modal(props: &ComponentProps) -> Html {
 #[function_component(CardCreateModal)]
 pub fn card_create_modal(props: &CardCreateModalProps) -> Html {
     let context = use_context::<AppContext>().expect("no ctx found");
-    let team_id = {
-        let current_team = context.current_team.clone();
-        let team = context.teams.clone().into_iter().find(|t| {
-            if let Some(team) = current_team.clone() {
-                team == t.name.clone()
-            } else {
-                false
-            }
-        });
-        match team {
-            Some(t) => t.id.clone(),
-            None => String::from(""),
-        }
-    };
+    let team_id = context.current_team_id();
     let new_ticket = use_state(|| NewTicket {
-        title: String::from(""),
-        description: String::from(""),
-        assigned_to: String::from(""),
         status: props.status.clone(),
         team_id: team_id.clone(),
+        ..Default::default()
     });
+    let template_id = use_state(String::new);
 
     let reset_ticket = {
         let new_ticket = new_ticket.clone();
+        let template_id = template_id.clone();
         let status = props.status.clone();
         let team_id = team_id.clone();
         Callback::from(move |_| {
+            template_id.set(String::new());
             new_ticket.set(NewTicket {
-                title: "".into(),
-                description: "".into(),
-                assigned_to: "".into(),
                 status: status.clone(),
                 team_id: team_id.clone(),
+                ..Default::default()
             });
         })
     };
 
+    let apply_template = {
+        let new_ticket = new_ticket.clone();
+        let template_id = template_id.clone();
+        let reset_ticket = reset_ticket.clone();
+        let templates = context.templates.clone();
+        let team_name = context.current_team.clone().unwrap_or_default();
+        let status = props.status.clone();
+        Callback::from(move |e: Event| {
+            let id = e.target_dyn_into::<HtmlSelectElement>().unwrap().value();
+            template_id.set(id.clone());
+            let template = match templates.iter().find(|t| t.id == id) {
+                Some(template) => template.clone(),
+                // "None" goes back to a blank ticket.
+                None => {
+                    reset_ticket.emit(());
+                    return;
+                }
+            };
+            let Template {
+                title,
+                description,
+                labels,
+                owner,
+                status: template_status,
+                checklist,
+                ..
+            } = template;
+            new_ticket.set(NewTicket {
+                title: expand_title(&title, &team_name),
+                description,
+                labels,
+                owner: owner.unwrap_or_default(),
+                status: template_status.unwrap_or_else(|| status.clone()),
+                checklist,
+                ..(*new_ticket).clone()
+            });
+        })
+    };
+
+    // New checklist items all start unchecked.
+    let status_blocked = completion_blocker(
+        context.team(),
+        &new_ticket.status,
+        (0, new_ticket.checklist.len()),
+    );
+
     let save_ticket = {
         let new_ticket = new_ticket.clone();
         let update_tickets = context.update_tickets.clone();
@@ -135,86 +199,305 @@ pub fn card_create_modal(props: &CardCreateModalProps) -> Html {
         })
     };
 
-    html! {
-        <Modal close={props.close.clone()}>
-            <StyledCardCreateModal>
-                <div class="new-ticket-group">
-                    <span>{"Title:"}</span>
-                    <div class="new-ticket-input-group">
-                        <input
-                            type="text"
-                            value={new_ticket.title.clone()}
-                            onchange={{
-                                let new_ticket = new_ticket.clone();
-                                Callback::from(move |e: Event| {
-                                    new_ticket.set(NewTicket {
-                                        title: e.target_dyn_into::<HtmlInputElement>().unwrap().value(),
-                                        description: new_ticket.description.clone(),
-                                        status: new_ticket.status.clone(),
-                                        assigned_to: new_ticket.assigned_to.clone(),
-                                        team_id: new_ticket.team_id.clone(),
-                                    });
-                                })
-                            }}
-                        />
-                    </div>
+    let builtin_row = |key: &str| match key {
+        "title" => html! {
+            <div class="new-ticket-group">
+                <span>{"Title:"}</span>
+                <div class="new-ticket-input-group">
+                    <input
+                        type="text"
+                        value={new_ticket.title.clone()}
+                        onchange={{
+                            let new_ticket = new_ticket.clone();
+                            Callback::from(move |e: Event| {
+                                new_ticket.set(NewTicket {
+                                    title: e.target_dyn_into::<HtmlInputElement>().unwrap().value(),
+                                    ..(*new_ticket).clone()
+                                });
+                            })
+                        }}
+                    />
                 </div>
-                <div class="new-ticket-group">
-                    <span>{"Description:"}</span>
-                    <div class="new-ticket-input-group">
-                        <textarea
-                            value={new_ticket.description.clone()}
-                            onchange={{
-                                let new_ticket = new_ticket.clone();
-                                Callback::from(move |e: Event| {
-                                    new_ticket.set(NewTicket {
-                                        title: new_ticket.title.clone(),
-                                        description: e.target_dyn_into::<HtmlTextAreaElement>().unwrap().value(),
-                                        status: new_ticket.status.clone(),
-                                        assigned_to: new_ticket.assigned_to.clone(),
-                                        team_id: new_ticket.team_id.clone(),
-                                    });
-                                })
-                            }}
-                        />
-                    </div>
+            </div>
+        },
+        "description" => html! {
+            <div class="new-ticket-group">
+                <span>{"Description:"}</span>
+                <div class="new-ticket-input-group">
+                    <MarkdownEditor
+                        value={new_ticket.description.clone()}
+                        onchange={{
+                            let new_ticket = new_ticket.clone();
+                            Callback::from(move |description: String| {
+                                new_ticket.set(NewTicket {
+                                    description,
+                                    ..(*new_ticket).clone()
+                                });
+                            })
+                        }}
+                    />
                 </div>
-                <div class="new-ticket-group">
-                    <span>{"Assigned To:"}</span>
-                    <div class="new-ticket-input-group">
-                        <select
-                            value={new_ticket.assigned_to.clone()}
-                            onchange={{
-                                let new_ticket = new_ticket.clone();
-                                Callback::from(move |e: Event| {
-                                    new_ticket.set(NewTicket {
-                                        title: new_ticket.title.clone(),
-                                        description: new_ticket.description.clone(),
-                                        status: new_ticket.status.clone(),
-                                        assigned_to: e.target_dyn_into::<HtmlSelectElement>().unwrap().value(),
-                                        team_id: new_ticket.team_id.clone(),
-                                    });
-                                })
-                            }}
-                        >
-                            <option
-                                default={true}
-                                selected={new_ticket.assigned_to.clone() == String::from("")}
-                            >{"Unassigned"}</option>
-                            {context.users.clone().into_iter().map(|user| {
-                                html! {
-                                    <option
-                                        value={user.id.clone()}
-                                        selected={user.id.clone() == new_ticket.assigned_to.clone()}
-                                    >{user.name.clone()}</option>
-                                }
-                            }).collect::<Html>()}
-                        </select>
-                    </div>
+            </div>
+        },
+        "status" => html! {
+            <div class="new-ticket-group">
+                <span>{"Status:"}</span>
+                <div class="new-ticket-input-group">
+                    <select
+                        value={new_ticket.status.clone()}
+                        onchange={{
+                            let new_ticket = new_ticket.clone();
+                            Callback::from(move |e: Event| {
+                                new_ticket.set(NewTicket {
+                                    status: e.target_dyn_into::<HtmlSelectElement>().unwrap().value(),
+                                    ..(*new_ticket).clone()
+                                });
+                            })
+                        }}
+                    >
+                        {STATUSES.iter().map(|status| {
+                            html! {
+                                <option
+                                    value={status.value}
+                                    selected={status.value == new_ticket.status}
+                                >{status.label}</option>
+                            }
+                        }).collect::<Html>()}
+                    </select>
                 </div>
+            </div>
+        },
+        "owner" => html! {
+            <div class="new-ticket-group">
+                <span>{"Owner:"}</span>
+                <div class="new-ticket-input-group">
+                    <select
+                        value={new_ticket.owner.clone()}
+                        onchange={{
+                            let new_ticket = new_ticket.clone();
+                            Callback::from(move |e: Event| {
+                                new_ticket.set(NewTicket {
+                                    owner: e.target_dyn_into::<HtmlSelectElement>().unwrap().value(),
+                                    ..(*new_ticket).clone()
+                                });
+                            })
+                        }}
+                    >
+                        <option
+                            default={true}
+                            selected={new_ticket.owner.is_empty()}
+                        >{"No owner"}</option>
+                        {context.users.clone().into_iter().map(|user| {
+                            html! {
+                                <option
+                                    value={user.id.clone()}
+                                    selected={user.id.clone() == new_ticket.owner.clone()}
+                                >{user.name.clone()}</option>
+                            }
+                        }).collect::<Html>()}
+                    </select>
+                </div>
+            </div>
+        },
+        "reviewers" => html! {
+            <div class="new-ticket-group">
+                <span>{"Reviewers:"}</span>
+                <div class="new-ticket-input-group">
+                    <UserPicker
+                        role="reviewer"
+                        selected={new_ticket.reviewers.clone()}
+                        onchange={{
+                            let new_ticket = new_ticket.clone();
+                            Callback::from(move |reviewers: Vec<String>| {
+                                new_ticket.set(NewTicket {
+                                    reviewers,
+                                    ..(*new_ticket).clone()
+                                });
+                            })
+                        }}
+                    />
+                </div>
+            </div>
+        },
+        "due_date" => html! {
+            <div class="new-ticket-group">
+                <span>{"Due Date:"}</span>
+                <div class="new-ticket-input-group">
+                    <input
+                        type="date"
+                        value={new_ticket.due_date.clone()}
+                        onchange={{
+                            let new_ticket = new_ticket.clone();
+                            Callback::from(move |e: Event| {
+                                new_ticket.set(NewTicket {
+                                    due_date: e.target_dyn_into::<HtmlInputElement>().unwrap().value(),
+                                    ..(*new_ticket).clone()
+                                });
+                            })
+                        }}
+                    />
+                </div>
+            </div>
+        },
+        "priority" => html! {
+            <div class="new-ticket-group">
+                <span>{"Priority:"}</span>
+                <div class="new-ticket-input-group">
+                    <select
+                        value={new_ticket.priority.clone()}
+                        onchange={{
+                            let new_ticket = new_ticket.clone();
+                            Callback::from(move |e: Event| {
+                                new_ticket.set(NewTicket {
+                                    priority: e.target_dyn_into::<HtmlSelectElement>().unwrap().value(),
+                                    ..(*new_ticket).clone()
+                                });
+                            })
+                        }}
+                    >
+                        <option value="" selected={new_ticket.priority.is_empty()}>{"None"}</option>
+                        {PRIORITIES.iter().map(|priority| {
+                            html! {
+                                <option
+                                    value={priority.value}
+                                    selected={priority.value == new_ticket.priority}
+                                >{priority.label}</option>
+                            }
+                        }).collect::<Html>()}
+                    </select>
+                </div>
+            </div>
+        },
+        "severity" => html! {
+            <div class="new-ticket-group">
+                <span>{"Severity:"}</span>
+                <div class="new-ticket-input-group">
+                    <select
+                        value={new_ticket.severity.clone()}
+                        onchange={{
+                            let new_ticket = new_ticket.clone();
+                            Callback::from(move |e: Event| {
+                                new_ticket.set(NewTicket {
+                                    severity: e.target_dyn_into::<HtmlSelectElement>().unwrap().value(),
+                                    ..(*new_ticket).clone()
+                                });
+                            })
+                        }}
+                    >
+                        <option value="" selected={new_ticket.severity.is_empty()}>{"None"}</option>
+                        {SEVERITIES.iter().map(|severity| {
+                            html! {
+                                <option
+                                    value={severity.value}
+                                    selected={severity.value == new_ticket.severity}
+                                >{severity.label}</option>
+                            }
+                        }).collect::<Html>()}
+                    </select>
+                </div>
+            </div>
+        },
+        "labels" => html! {
+            <div class="new-ticket-group">
+                <span>{"Labels:"}</span>
+                <div class="new-ticket-input-group">
+                    <LabelPicker
+                        selected={new_ticket.labels.clone()}
+                        onchange={{
+                            let new_ticket = new_ticket.clone();
+                            Callback::from(move |labels: Vec<String>| {
+                                new_ticket.set(NewTicket {
+                                    labels,
+                                    ..(*new_ticket).clone()
+                                });
+                            })
+                        }}
+                    />
+                </div>
+            </div>
+        },
+        _ => html!(),
+    };
+
+    let custom_row = |field: &CustomField| {
+        let onchange = {
+            let id = field.id.clone();
+            let new_ticket = new_ticket.clone();
+            Callback::from(move |value: Value| {
+                let mut custom_fields = new_ticket.custom_fields.clone();
+                custom_fields.insert(id.clone(), value);
+                new_ticket.set(NewTicket {
+                    custom_fields,
+                    ..(*new_ticket).clone()
+                });
+            })
+        };
+        html! {
+            <div class="new-ticket-group">
+                <span>{format!("{}:", field.name)}</span>
+                <div class="new-ticket-input-group">
+                    <CustomFieldInput
+                        field={field.clone()}
+                        value={new_ticket.custom_fields.get(&field.id).cloned().unwrap_or(Value::Null)}
+                        {onchange}
+                    />
+                </div>
+            </div>
+        }
+    };
+
+    html! {
+        <Modal close={props.close.clone()}>
+            <StyledCardCreateModal>
+                {if context.templates.is_empty() {
+                    html!()
+                } else {
+                    html! {
+                        <div class="new-ticket-group">
+                            <span>{"Template:"}</span>
+                            <div class="new-ticket-input-group">
+                                <select onchange={apply_template}>
+                                    <option value="" selected={template_id.is_empty()}>{"None"}</option>
+                                    {context.templates.iter().map(|template| {
+                                        html! {
+                                            <option
+                                                value={template.id.clone()}
+                                                selected={template.id == *template_id}
+                                            >{template.name.clone()}</option>
+                                        }
+                                    }).collect::<Html>()}
+                                </select>
+                            </div>
+                        </div>
+                    }
+                }}
+                {form_fields(context.team(), &context.fields).into_iter().map(|field| match field {
+                    FormField::Builtin(builtin) => builtin_row(builtin.key),
+                    FormField::Custom(field) => custom_row(field),
+                }).collect::<Html>()}
+                {if new_ticket.checklist.is_empty() {
+                    html!()
+                } else {
+                    html! {
+                        <div class="new-ticket-group">
+                            <span>{"Checklist:"}</span>
+                            <div class="new-ticket-input-group">
+                                <ul class="new-ticket-checklist">
+                                    {new_ticket.checklist.iter().map(|item| {
+                                        html!(<li>{item.clone()}</li>)
+                                    }).collect::<Html>()}
+                                </ul>
+                            </div>
+                        </div>
+                    }
+                }}
                 <div class="new-ticket-action-group">
-                    <button onclick={reset_ticket}>{"Reset"}</button>
-                    <button onclick={save_ticket}>{"Save"}</button>
+                    {match status_blocked.clone() {
+                        Some(reason) => html!(<span class="new-ticket-error">{reason}</span>),
+                        None => html!(),
+                    }}
+                    <button onclick={reset_ticket.reform(|_| ())}>{"Reset"}</button>
+                    <button disabled={status_blocked.is_some()} onclick={save_ticket}>{"Save"}</button>
                 </div>
             </StyledCardCreateModal>
         </Modal>
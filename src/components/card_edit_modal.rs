@@ -1,17 +1,39 @@
-use super::modal::Modal;
-use crate::{common::ComponentProps, AppContext, Ticket};
+use super::{
+    activity_log::ActivityLog,
+    archive_modal::set_archived,
+    attachment_panel::AttachmentPanel,
+    checklist::{status_blocker, Checklist},
+    comment_panel::CommentPanel,
+    custom_fields::{form_fields, CustomFieldRow, FormField},
+    label_picker::{label_chip, LabelPicker},
+    markdown_editor::{Markdown, MarkdownEditor},
+    modal::Modal,
+    subtasks::Subtasks,
+    ticket_links::TicketLinks,
+    user_picker::{avatar, UserPicker},
+    watchers::Watchers,
+};
+use crate::{
+    common::ComponentProps,
+    time::{due_badge, local_datetime},
+    AppContext, Ticket, TicketDialog,
+};
 use gloo_net::http::Request;
-use serde_json::json;
+use serde_json::{json, Value};
+use std::rc::Rc;
 use stylist::yew::styled_component;
 use wasm_bindgen_futures::spawn_local;
-use web_sys::{Event, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+use web_sys::{Event, HtmlInputElement, HtmlSelectElement};
 use yew::{
     function_component, html, use_context, use_state, Callback, Html, Properties, TargetCast,
 };
 
 #[derive(Properties, PartialEq)]
 pub struct CardEditModalProps {
-    pub ticket: Ticket,
+    pub ticket: Rc<Ticket>,
+    /// Field to open in edit mode, e.g. `"owner"`.
+    #[prop_or_default]
+    pub editing: Option<String>,
     pub close: Callback<()>,
 }
 
@@ -40,7 +62,77 @@ pub const STATUSES: &[Status] = &[
     },
 ];
 
-async fn update_ticket(id: String, key: String, value: String) -> Vec<Ticket> {
+/// Display name of a status value, or the value itself if it is unknown.
+pub fn status_label(value: &str) -> &str {
+    STATUSES
+        .iter()
+        .find(|status| status.value == value)
+        .map(|status| status.label)
+        .unwrap_or(value)
+}
+
+/// A ranked ticket attribute such as priority or severity, most urgent first.
+#[derive(Clone)]
+pub struct Level {
+    pub value: &'static str,
+    pub label: &'static str,
+    pub color: &'static str,
+}
+
+pub const PRIORITIES: &[Level] = &[
+    Level {
+        value: "P0",
+        label: "P0 - Blocker",
+        color: "#d32f2f",
+    },
+    Level {
+        value: "P1",
+        label: "P1 - High",
+        color: "#f57c00",
+    },
+    Level {
+        value: "P2",
+        label: "P2 - Medium",
+        color: "#fbc02d",
+    },
+    Level {
+        value: "P3",
+        label: "P3 - Low",
+        color: "#9e9e9e",
+    },
+];
+
+pub const SEVERITIES: &[Level] = &[
+    Level {
+        value: "Critical",
+        label: "Critical",
+        color: "#d32f2f",
+    },
+    Level {
+        value: "Major",
+        label: "Major",
+        color: "#f57c00",
+    },
+    Level {
+        value: "Minor",
+        label: "Minor",
+        color: "#1976d2",
+    },
+    Level {
+        value: "Trivial",
+        label: "Trivial",
+        color: "#9e9e9e",
+    },
+];
+
+/// Looks up the level for `value`, if it is set and known.
+pub fn find_level(levels: &'static [Level], value: &Option<String>) -> Option<&'static Level> {
+    value
+        .as_ref()
+        .and_then(|value| levels.iter().find(|l| l.value == value))
+}
+
+pub async fn update_ticket(id: String, key: String, value: Value) -> Vec<Ticket> {
     let mut tickets = vec![];
     let response = Request::put(&format!("/api/tickets/{}", id))
         .json(&json!({
@@ -55,7 +147,17 @@ async fn update_ticket(id: String, key: String, value: String) -> Vec<Ticket> {
     tickets
 }
 
-async fn delete_ticket(id: String) {
+/// Fetches a ticket by id or by key such as "QE-142".
+pub async fn fetch_ticket(id: String) -> Option<Ticket> {
+    let response = Request::get(&format!("/api/tickets/{}", id)).send().await;
+    match response {
+        Ok(r) => r.json::<Ticket>().await.ok(),
+        Err(_) => None,
+    }
+}
+
+/// Moves a ticket to its team's trash, see `trash_modal`.
+pub async fn delete_ticket(id: String) {
     let _response = Request::delete(&format!("/api/tickets/{}", id))
         .send()
         .await;
@@ -86,15 +188,62 @@ fn styled_card_edit_modal(props: &ComponentProps) -> Html {
             .card-detail-title span {
                 font-size: 22px;
             }
+            .card-detail-title span.card-detail-key {
+                color: #808080;
+                margin-right: 8px;
+            }
             .card-detail-title input {
                 width: 100%;
             }
-            .card-detail-desc textarea {
-                margin-left: 3px;
-                width: 100%;
-                height: 65px;
-                font-family: sans-serif;
-                resize: none;
+            .card-detail-desc .card-detail-label-group {
+                min-width: 0;
+            }
+            .card-detail-level {
+                color: #fff;
+                border-radius: 3px;
+                padding: 1px 6px;
+            }
+            .card-detail-error {
+                margin-left: 8px;
+                font-size: 12px;
+                color: #f44336;
+            }
+            .card-detail-due {
+                margin-left: 8px;
+            }
+            .card-detail-users {
+                display: flex;
+                flex-wrap: wrap;
+                gap: 8px;
+            }
+            .card-detail-user {
+                display: flex;
+                align-items: center;
+                gap: 4px;
+            }
+            .custom-field-options {
+                display: flex;
+                flex-wrap: wrap;
+                gap: 8px;
+            }
+            .card-detail-labels {
+                display: flex;
+                flex-wrap: wrap;
+                gap: 4px;
+            }
+            .label-chip {
+                color: #fff;
+                font-size: 12px;
+                border-radius: 10px;
+                padding: 2px 8px;
+            }
+            .card-detail-meta {
+                margin-top: 20px;
+                font-size: 12px;
+                color: #808080;
+            }
+            .card-detail-meta > div {
+                margin-top: 4px;
             }
             .card-detail-action-group {
                 display: flex;
@@ -107,6 +256,13 @@ fn styled_card_edit_modal(props: &ComponentProps) -> Html {
                 border-radius: 3px;
                 border: none;
                 padding: 4px 6px 3px;
+                margin-left: 5px;
+            }
+            .card-detail-action-group > button.move-team {
+                background-color: #808080;
+            }
+            .card-detail-action-group > button.archive {
+                background-color: #808080;
             }
             button {
                 cursor: pointer;
@@ -118,45 +274,107 @@ fn styled_card_edit_modal(props: &ComponentProps) -> Html {
 #[function_component(CardEditModal)]
 pub fn card_edit_modal(props: &CardEditModalProps) -> Html {
     let context = use_context::<AppContext>().expect("no ctx found");
-    let editing_title = use_state(|| false);
-    let editing_description = use_state(|| false);
-    let editing_assigned_to = use_state(|| false);
-    let editing_status = use_state(|| false);
+    let editing = props.editing.clone().unwrap_or_default();
+    let editing_title = use_state(|| editing == "title");
+    let editing_description = use_state(|| editing == "description");
+    let editing_owner = use_state(|| editing == "owner");
+    let editing_reviewers = use_state(|| editing == "reviewers");
+    let editing_status = use_state(|| editing == "status");
+    let editing_due_date = use_state(|| editing == "due_date");
+    let editing_priority = use_state(|| editing == "priority");
+    let editing_severity = use_state(|| editing == "severity");
+    let editing_labels = use_state(|| editing == "labels");
     let updated_title = use_state(|| props.ticket.title.clone());
     let updated_description = use_state(|| props.ticket.description.clone());
     let updated_status = use_state(|| props.ticket.status.clone());
-    let updated_assigned_to = use_state(|| match props.ticket.assigned_to.clone() {
-        Some(assigned_to) => assigned_to.id,
+    let updated_owner = use_state(|| match props.ticket.owner() {
+        Some(owner) => owner.id.clone(),
         None => String::from(""),
     });
+    let updated_reviewers = use_state(|| {
+        props
+            .ticket
+            .reviewers
+            .iter()
+            .map(|u| u.id.clone())
+            .collect::<Vec<String>>()
+    });
+    let updated_due_date = use_state(|| props.ticket.due_date.clone().unwrap_or_default());
+    let updated_priority = use_state(|| props.ticket.priority.clone().unwrap_or_default());
+    let updated_severity = use_state(|| props.ticket.severity.clone().unwrap_or_default());
+    let updated_labels = use_state(|| {
+        props
+            .ticket
+            .labels
+            .iter()
+            .map(|l| l.id.clone())
+            .collect::<Vec<String>>()
+    });
+
+    let status_blocked = status_blocker(context.team(), &props.ticket, &updated_status);
 
     let save_ticket = {
         let editing_title = editing_title.clone();
         let editing_description = editing_description.clone();
-        let editing_assigned_to = editing_assigned_to.clone();
+        let editing_owner = editing_owner.clone();
+        let editing_reviewers = editing_reviewers.clone();
         let editing_status = editing_status.clone();
+        let editing_due_date = editing_due_date.clone();
+        let editing_priority = editing_priority.clone();
+        let editing_severity = editing_severity.clone();
+        let editing_labels = editing_labels.clone();
         let updated_title = updated_title.clone();
         let updated_description = updated_description.clone();
-        let updated_assigned_to = updated_assigned_to.clone();
+        let updated_owner = updated_owner.clone();
         let updated_status = updated_status.clone();
+        let updated_reviewers = updated_reviewers.clone();
+        let updated_due_date = updated_due_date.clone();
+        let updated_priority = updated_priority.clone();
+        let updated_severity = updated_severity.clone();
+        let updated_labels = updated_labels.clone();
         let id = props.ticket.id.clone();
         let update_tickets = context.update_tickets.clone();
         Callback::from(move |field: String| {
             let id = id.clone();
             let update_tickets = update_tickets.clone();
             let mut state = editing_title.clone();
-            let mut value = (*updated_title).clone();
+            let mut value = Value::from((*updated_title).clone());
             if field == String::from("description") {
                 state = editing_description.clone();
-                value = (*updated_description).clone();
+                value = (*updated_description).clone().into();
+            }
+            if field == "owner" {
+                state = editing_owner.clone();
+                value = (*updated_owner).clone().into();
             }
-            if field == String::from("assigned_to") {
-                state = editing_assigned_to.clone();
-                value = (*updated_assigned_to).clone();
+            if field == "reviewers" {
+                state = editing_reviewers.clone();
+                value = json!(*updated_reviewers);
             }
             if field == String::from("status") {
                 state = editing_status.clone();
-                value = (*updated_status).clone();
+                value = (*updated_status).clone().into();
+            }
+            if field == "due_date" {
+                state = editing_due_date.clone();
+                // An empty date clears the due date.
+                value = if updated_due_date.is_empty() {
+                    Value::Null
+                } else {
+                    (*updated_due_date).clone().into()
+                };
+            }
+            if field == "priority" {
+                state = editing_priority.clone();
+                value = (*updated_priority).clone().into();
+            }
+            if field == "severity" {
+                state = editing_severity.clone();
+                value = (*updated_severity).clone().into();
+            }
+            if field == "labels" {
+                state = editing_labels.clone();
+                value = json!(*updated_labels);
             }
             spawn_local(async move {
                 update_ticket(id, field, value).await;
@@ -166,7 +384,35 @@ pub fn card_edit_modal(props: &CardEditModalProps) -> Html {
         })
     };
 
+    let open_move_team = {
+        let ticket = props.ticket.clone();
+        let update_ticket_dialog = context.update_ticket_dialog.clone();
+        Callback::from(move |_| {
+            update_ticket_dialog.emit(Some(TicketDialog::MoveTeam(ticket.clone())));
+        })
+    };
+
     let delete_ticket = {
+        let ticket = props.ticket.clone();
+        let update_tickets = context.update_tickets.clone();
+        let update_deleted_ticket = context.update_deleted_ticket.clone();
+        let close = props.close.clone();
+        Callback::from(move |_| {
+            let ticket = ticket.clone();
+            let update_tickets = update_tickets.clone();
+            let update_deleted_ticket = update_deleted_ticket.clone();
+            let close = close.clone();
+            spawn_local(async move {
+                delete_ticket(ticket.id.clone()).await;
+                update_tickets.emit(());
+                update_deleted_ticket.emit(Some(ticket));
+                close.emit(());
+            });
+        })
+    };
+
+    let archived = props.ticket.archived_at.is_some();
+    let toggle_archived = {
         let id = props.ticket.id.clone();
         let update_tickets = context.update_tickets.clone();
         let close = props.close.clone();
@@ -175,273 +421,684 @@ pub fn card_edit_modal(props: &CardEditModalProps) -> Html {
             let update_tickets = update_tickets.clone();
             let close = close.clone();
             spawn_local(async move {
-                delete_ticket(id).await;
+                set_archived(id, !archived).await;
                 update_tickets.emit(());
-                close.emit(());
+                if !archived {
+                    close.emit(());
+                }
             });
         })
     };
 
-    html! {
-        <Modal close={props.close.clone()}>
-            <StyledCardEditModal>
-                <div class="card-detail-group card-detail-title">
-                    {if *editing_title {
-                        html! {
-                            <>
-                                <div class="card-detail-label-group">
-                                    <input
-                                        type="text"
-                                        value={(*updated_title).clone()}
-                                        onchange={{
-                                            let updated_title = updated_title.clone();
-                                            Callback::from(move |e: Event| {
-                                                updated_title.set(
-                                                    e.target_dyn_into::<HtmlInputElement>()
-                                                        .unwrap()
-                                                        .value()
-                                                );
-                                            })
-                                        }}
-                                    />
-                                </div>
-                                <div class="card-detail-button-group">
-                                    <button onclick={{
-                                        let editing_title = editing_title.clone();
-                                        Callback::from(move |_| {
-                                            editing_title.set(false);
+    let builtin_row = |key: &str| match key {
+        "title" => html! {
+            <div class="card-detail-group card-detail-title">
+                {if *editing_title {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group">
+                                <input
+                                    type="text"
+                                    value={(*updated_title).clone()}
+                                    onchange={{
+                                        let updated_title = updated_title.clone();
+                                        Callback::from(move |e: Event| {
+                                            updated_title.set(
+                                                e.target_dyn_into::<HtmlInputElement>()
+                                                    .unwrap()
+                                                    .value()
+                                            );
                                         })
-                                    }}>{"Cancel"}</button>
-                                    <button onclick={{
-                                        let save_ticket = save_ticket.clone();
-                                        Callback::from(move |_| {
-                                            save_ticket.emit(String::from("title"));
+                                    }}
+                                />
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_title = editing_title.clone();
+                                    Callback::from(move |_| {
+                                        editing_title.set(false);
+                                    })
+                                }}>{"Cancel"}</button>
+                                <button onclick={{
+                                    let save_ticket = save_ticket.clone();
+                                    Callback::from(move |_| {
+                                        save_ticket.emit(String::from("title"));
+                                    })
+                                }}>{"Save"}</button>
+                            </div>
+                        </>
+                    }
+                } else {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group">
+                                {match &props.ticket.key {
+                                    Some(key) => html!(<span class="card-detail-key">{key.clone()}</span>),
+                                    None => html!(),
+                                }}
+                                <span>{props.ticket.title.clone()}</span>
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_title = editing_title.clone();
+                                    Callback::from(move |_| {
+                                        editing_title.set(true);
+                                    })
+                                }}>{"Edit"}</button>
+                            </div>
+                        </>
+                    }
+                }}
+            </div>
+        },
+        "description" => html! {
+            <div class="card-detail-group card-detail-desc">
+                <span>{"Description: "}</span>
+                {if *editing_description {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group">
+                                <MarkdownEditor
+                                    value={(*updated_description).clone()}
+                                    onchange={{
+                                        let updated_description = updated_description.clone();
+                                        Callback::from(move |description: String| {
+                                            updated_description.set(description);
                                         })
-                                    }}>{"Save"}</button>
-                                </div>
-                            </>
-                        }
-                    } else {
-                        html! {
-                            <>
-                                <div class="card-detail-label-group">
-                                    <span>{props.ticket.title.clone()}</span>
-                                </div>
-                                <div class="card-detail-button-group">
-                                    <button onclick={{
-                                        let editing_title = editing_title.clone();
-                                        Callback::from(move |_| {
-                                            editing_title.set(true);
+                                    }}
+                                />
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_description = editing_description.clone();
+                                    Callback::from(move |_| {
+                                        editing_description.set(false);
+                                    })
+                                }}>{"Cancel"}</button>
+                                <button onclick={{
+                                    let save_ticket = save_ticket.clone();
+                                    Callback::from(move |_| {
+                                        save_ticket.emit(String::from("description"));
+                                    })
+                                }}>{"Save"}</button>
+                            </div>
+                        </>
+                    }
+                } else {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group">
+                                <Markdown source={props.ticket.description.clone()} />
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_description = editing_description.clone();
+                                    Callback::from(move |_| {
+                                        editing_description.set(true);
+                                    })
+                                }}>{"Edit"}</button>
+                            </div>
+                        </>
+                    }
+                }}
+            </div>
+        },
+        "owner" => html! {
+            <div class="card-detail-group">
+                <span>{"Owner: "}</span>
+                {if *editing_owner {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group">
+                                <select
+                                    value={(*updated_owner).clone()}
+                                    onchange={{
+                                        let updated_owner = updated_owner.clone();
+                                        Callback::from(move |e: Event| {
+                                            updated_owner.set(
+                                                e.target_dyn_into::<HtmlSelectElement>()
+                                                    .unwrap()
+                                                    .value()
+                                            );
                                         })
-                                    }}>{"Edit"}</button>
-                                </div>
-                            </>
-                        }
-                    }}
-                </div>
-                <div class="card-detail-group card-detail-desc">
-                    <span>{"Description: "}</span>
-                    {if *editing_description {
-                        html! {
-                            <>
-                                <div class="card-detail-label-group">
-                                    <textarea
-                                        value={(*updated_description).clone()}
-                                        onchange={{
-                                            let updated_description = updated_description.clone();
-                                            Callback::from(move |e: Event| {
-                                                updated_description.set(
-                                                    e.target_dyn_into::<HtmlTextAreaElement>()
-                                                        .unwrap()
-                                                        .value()
-                                                );
-                                            })
-                                        }}
-                                    />
-                                </div>
-                                <div class="card-detail-button-group">
-                                    <button onclick={{
-                                        let editing_description = editing_description.clone();
-                                        Callback::from(move |_| {
-                                            editing_description.set(false);
+                                    }}
+                                >
+                                    <option
+                                        default={true}
+                                        selected={(*updated_owner).clone() == ""}
+                                        value={""}
+                                    >{"No owner"}</option>
+                                    {context.users.clone().into_iter().map(|user| {
+                                        html! {
+                                            <option
+                                                value={user.id.clone()}
+                                                selected={(*updated_owner).clone() == user.id.clone()}
+                                            >{user.name}</option>
+                                        }
+                                    }).collect::<Html>()}
+                                </select>
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_owner = editing_owner.clone();
+                                    Callback::from(move |_| {
+                                        editing_owner.set(false);
+                                    })
+                                }}>{"Cancel"}</button>
+                                <button onclick={{
+                                    let save_ticket = save_ticket.clone();
+                                    Callback::from(move |_| {
+                                        save_ticket.emit(String::from("owner"));
+                                    })
+                                }}>{"Save"}</button
+                                >
+                            </div>
+                        </>
+                    }
+                } else {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group">
+                                {if let Some(owner) = props.ticket.owner() {
+                                    html!(<span>{owner.name.clone()}</span>)
+                                } else {
+                                    html!(<span>{"No owner"}</span>)
+                                }}
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_owner = editing_owner.clone();
+                                    Callback::from(move |_| {
+                                        editing_owner.set(true);
+                                    })
+                                }}>{"Edit"}</button>
+                            </div>
+                        </>
+                    }
+                }}
+            </div>
+        },
+        "reviewers" => html! {
+            <div class="card-detail-group">
+                <span>{"Reviewers: "}</span>
+                {if *editing_reviewers {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group">
+                                <UserPicker
+                                    role="reviewer"
+                                    selected={(*updated_reviewers).clone()}
+                                    onchange={{
+                                        let updated_reviewers = updated_reviewers.clone();
+                                        Callback::from(move |reviewers: Vec<String>| {
+                                            updated_reviewers.set(reviewers);
                                         })
-                                    }}>{"Cancel"}</button>
-                                    <button onclick={{
-                                        let save_ticket = save_ticket.clone();
-                                        Callback::from(move |_| {
-                                            save_ticket.emit(String::from("description"));
+                                    }}
+                                />
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_reviewers = editing_reviewers.clone();
+                                    Callback::from(move |_| {
+                                        editing_reviewers.set(false);
+                                    })
+                                }}>{"Cancel"}</button>
+                                <button onclick={{
+                                    let save_ticket = save_ticket.clone();
+                                    Callback::from(move |_| {
+                                        save_ticket.emit(String::from("reviewers"));
+                                    })
+                                }}>{"Save"}</button>
+                            </div>
+                        </>
+                    }
+                } else {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group card-detail-users">
+                                {if props.ticket.reviewers.is_empty() {
+                                    html!(<span>{"No reviewers"}</span>)
+                                } else {
+                                    props.ticket.reviewers.iter().map(|reviewer| {
+                                        html! {
+                                            <span class="card-detail-user">
+                                                {avatar(reviewer, "reviewer")}
+                                                {reviewer.name.clone()}
+                                            </span>
+                                        }
+                                    }).collect::<Html>()
+                                }}
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_reviewers = editing_reviewers.clone();
+                                    Callback::from(move |_| {
+                                        editing_reviewers.set(true);
+                                    })
+                                }}>{"Edit"}</button>
+                            </div>
+                        </>
+                    }
+                }}
+            </div>
+        },
+        "status" => html! {
+            <div class="card-detail-group">
+                <span>{"Status: "}</span>
+                {if *editing_status {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group">
+                                <select
+                                    value={(*updated_status).clone()}
+                                    onchange={{
+                                        let updated_status = updated_status.clone();
+                                        Callback::from(move |e: Event| {
+                                            updated_status.set(
+                                                e.target_dyn_into::<HtmlSelectElement>()
+                                                    .unwrap()
+                                                    .value()
+                                            );
                                         })
-                                    }}>{"Save"}</button>
-                                </div>
-                            </>
-                        }
-                    } else {
-                        html! {
-                            <>
-                                <div class="card-detail-label-group">
-                                    <span>{props.ticket.description.clone()}</span>
-                                </div>
-                                <div class="card-detail-button-group">
-                                    <button onclick={{
-                                        let editing_description = editing_description.clone();
-                                        Callback::from(move |_| {
-                                            editing_description.set(true);
+                                    }}
+                                >
+                                    {STATUSES.clone().into_iter().map(|status| {
+                                        html!{
+                                            <option
+                                                value={status.value.clone()}
+                                                selected={status.value.clone() == (*updated_status).clone()}
+                                            >{status.label.clone()}</option>
+                                        }
+                                    }).collect::<Html>()}
+                                </select>
+                                {match status_blocked.clone() {
+                                    Some(reason) => html! {
+                                        <span class="card-detail-error">{reason}</span>
+                                    },
+                                    None => html!(),
+                                }}
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_status = editing_status.clone();
+                                    Callback::from(move |_| {
+                                        editing_status.set(false);
+                                    })
+                                }}>{"Cancel"}</button>
+                                <button disabled={status_blocked.is_some()} onclick={{
+                                    let save_ticket = save_ticket.clone();
+                                    Callback::from(move |_| {
+                                        save_ticket.emit(String::from("status"));
+                                    })
+                                }}>{"Save"}</button
+                                >
+                            </div>
+                        </>
+                    }
+                } else {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group">
+                                <span>{{
+                                    let status = props.ticket.status.clone();
+                                    STATUSES
+                                        .clone()
+                                        .into_iter()
+                                        .find(move |s| s.value == status)
+                                        .unwrap_or(&Status {
+                                            value: "",
+                                            label: "No Status",
                                         })
-                                    }}>{"Edit"}</button>
-                                </div>
-                            </>
-                        }
-                    }}
-                </div>
-                <div class="card-detail-group">
-                    <span>{"Assigned To: "}</span>
-                    {if *editing_assigned_to {
-                        html! {
-                            <>
-                                <div class="card-detail-label-group">
-                                    <select
-                                        value={(*updated_assigned_to).clone()}
-                                        onchange={{
-                                            let updated_assigned_to = updated_assigned_to.clone();
-                                            Callback::from(move |e: Event| {
-                                                updated_assigned_to.set(
-                                                    e.target_dyn_into::<HtmlSelectElement>()
-                                                        .unwrap()
-                                                        .value()
-                                                );
-                                            })
-                                        }}
-                                    >
-                                        <option
-                                            default={true}
-                                            selected={(*updated_assigned_to).clone() == ""}
-                                            value={""}
-                                        >{"Unassigned"}</option>
-                                        {context.users.clone().into_iter().map(|user| {
-                                            html! {
-                                                <option
-                                                    value={user.id.clone()}
-                                                    selected={(*updated_assigned_to).clone() == user.id.clone()}
-                                                >{user.name}</option>
-                                            }
-                                        }).collect::<Html>()}
-                                    </select>
-                                </div>
-                                <div class="card-detail-button-group">
-                                    <button onclick={{
-                                        let editing_assigned_to = editing_assigned_to.clone();
-                                        Callback::from(move |_| {
-                                            editing_assigned_to.set(false);
+                                        .label
+                                }}</span>
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_status = editing_status.clone();
+                                    Callback::from(move |_| {
+                                        editing_status.set(true);
+                                    })
+                                }}>{"Edit"}</button>
+                            </div>
+                        </>
+                    }
+                }}
+            </div>
+        },
+        "due_date" => html! {
+            <div class="card-detail-group">
+                <span>{"Due Date: "}</span>
+                {if *editing_due_date {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group">
+                                <input
+                                    type="date"
+                                    value={(*updated_due_date).clone()}
+                                    onchange={{
+                                        let updated_due_date = updated_due_date.clone();
+                                        Callback::from(move |e: Event| {
+                                            updated_due_date.set(
+                                                e.target_dyn_into::<HtmlInputElement>()
+                                                    .unwrap()
+                                                    .value()
+                                            );
                                         })
-                                    }}>{"Cancel"}</button>
-                                    <button onclick={{
-                                        let save_ticket = save_ticket.clone();
-                                        Callback::from(move |_| {
-                                            save_ticket.emit(String::from("assigned_to"));
+                                    }}
+                                />
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_due_date = editing_due_date.clone();
+                                    Callback::from(move |_| {
+                                        editing_due_date.set(false);
+                                    })
+                                }}>{"Cancel"}</button>
+                                <button onclick={{
+                                    let save_ticket = save_ticket.clone();
+                                    Callback::from(move |_| {
+                                        save_ticket.emit(String::from("due_date"));
+                                    })
+                                }}>{"Save"}</button>
+                            </div>
+                        </>
+                    }
+                } else {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group">
+                                {match props.ticket.due_date.clone() {
+                                    Some(due_date) => html! {
+                                        <>
+                                            <span>{due_date.clone()}</span>
+                                            {match due_badge(&due_date) {
+                                                Some((badge, color)) => html! {
+                                                    <span
+                                                        class="card-detail-level card-detail-due"
+                                                        style={format!("background-color: {};", color)}
+                                                    >{badge}</span>
+                                                },
+                                                None => html!(),
+                                            }}
+                                        </>
+                                    },
+                                    None => html!(<span>{"None"}</span>),
+                                }}
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_due_date = editing_due_date.clone();
+                                    Callback::from(move |_| {
+                                        editing_due_date.set(true);
+                                    })
+                                }}>{"Edit"}</button>
+                            </div>
+                        </>
+                    }
+                }}
+            </div>
+        },
+        "priority" => html! {
+            <div class="card-detail-group">
+                <span>{"Priority: "}</span>
+                {if *editing_priority {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group">
+                                <select
+                                    value={(*updated_priority).clone()}
+                                    onchange={{
+                                        let updated_priority = updated_priority.clone();
+                                        Callback::from(move |e: Event| {
+                                            updated_priority.set(
+                                                e.target_dyn_into::<HtmlSelectElement>()
+                                                    .unwrap()
+                                                    .value()
+                                            );
                                         })
-                                    }}>{"Save"}</button
-                                    >
-                                </div>
-                            </>
-                        }
-                    } else {
-                        html! {
-                            <>
-                                <div class="card-detail-label-group">
-                                    {if let Some(assigned_to) = props.ticket.assigned_to.clone() {
-                                        html!(<span>{assigned_to.name}</span>)
-                                    } else {
-                                        html!(<span>{"Unassigned"}</span>)
                                     }}
-                                </div>
-                                <div class="card-detail-button-group">
-                                    <button onclick={{
-                                        let editing_assigned_to = editing_assigned_to.clone();
-                                        Callback::from(move |_| {
-                                            editing_assigned_to.set(true);
+                                >
+                                    <option
+                                        value={""}
+                                        selected={updated_priority.is_empty()}
+                                    >{"None"}</option>
+                                    {PRIORITIES.iter().map(|level| {
+                                        html! {
+                                            <option
+                                                value={level.value}
+                                                selected={level.value == *updated_priority}
+                                            >{level.label}</option>
+                                        }
+                                    }).collect::<Html>()}
+                                </select>
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_priority = editing_priority.clone();
+                                    Callback::from(move |_| {
+                                        editing_priority.set(false);
+                                    })
+                                }}>{"Cancel"}</button>
+                                <button onclick={{
+                                    let save_ticket = save_ticket.clone();
+                                    Callback::from(move |_| {
+                                        save_ticket.emit(String::from("priority"));
+                                    })
+                                }}>{"Save"}</button>
+                            </div>
+                        </>
+                    }
+                } else {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group">
+                                {match find_level(PRIORITIES, &props.ticket.priority) {
+                                    Some(level) => html! {
+                                        <span
+                                            class="card-detail-level"
+                                            style={format!("background-color: {};", level.color)}
+                                        >{level.label}</span>
+                                    },
+                                    None => html!(<span>{"None"}</span>),
+                                }}
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_priority = editing_priority.clone();
+                                    Callback::from(move |_| {
+                                        editing_priority.set(true);
+                                    })
+                                }}>{"Edit"}</button>
+                            </div>
+                        </>
+                    }
+                }}
+            </div>
+        },
+        "severity" => html! {
+            <div class="card-detail-group">
+                <span>{"Severity: "}</span>
+                {if *editing_severity {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group">
+                                <select
+                                    value={(*updated_severity).clone()}
+                                    onchange={{
+                                        let updated_severity = updated_severity.clone();
+                                        Callback::from(move |e: Event| {
+                                            updated_severity.set(
+                                                e.target_dyn_into::<HtmlSelectElement>()
+                                                    .unwrap()
+                                                    .value()
+                                            );
                                         })
-                                    }}>{"Edit"}</button>
-                                </div>
-                            </>
-                        }
-                    }}
+                                    }}
+                                >
+                                    <option
+                                        value={""}
+                                        selected={updated_severity.is_empty()}
+                                    >{"None"}</option>
+                                    {SEVERITIES.iter().map(|level| {
+                                        html! {
+                                            <option
+                                                value={level.value}
+                                                selected={level.value == *updated_severity}
+                                            >{level.label}</option>
+                                        }
+                                    }).collect::<Html>()}
+                                </select>
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_severity = editing_severity.clone();
+                                    Callback::from(move |_| {
+                                        editing_severity.set(false);
+                                    })
+                                }}>{"Cancel"}</button>
+                                <button onclick={{
+                                    let save_ticket = save_ticket.clone();
+                                    Callback::from(move |_| {
+                                        save_ticket.emit(String::from("severity"));
+                                    })
+                                }}>{"Save"}</button>
+                            </div>
+                        </>
+                    }
+                } else {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group">
+                                {match find_level(SEVERITIES, &props.ticket.severity) {
+                                    Some(level) => html! {
+                                        <span
+                                            class="card-detail-level"
+                                            style={format!("background-color: {};", level.color)}
+                                        >{level.label}</span>
+                                    },
+                                    None => html!(<span>{"None"}</span>),
+                                }}
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_severity = editing_severity.clone();
+                                    Callback::from(move |_| {
+                                        editing_severity.set(true);
+                                    })
+                                }}>{"Edit"}</button>
+                            </div>
+                        </>
+                    }
+                }}
+            </div>
+        },
+        "labels" => html! {
+            <div class="card-detail-group">
+                <span>{"Labels: "}</span>
+                {if *editing_labels {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group">
+                                <LabelPicker
+                                    selected={(*updated_labels).clone()}
+                                    onchange={{
+                                        let updated_labels = updated_labels.clone();
+                                        Callback::from(move |labels: Vec<String>| {
+                                            updated_labels.set(labels);
+                                        })
+                                    }}
+                                />
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_labels = editing_labels.clone();
+                                    Callback::from(move |_| {
+                                        editing_labels.set(false);
+                                    })
+                                }}>{"Cancel"}</button>
+                                <button onclick={{
+                                    let save_ticket = save_ticket.clone();
+                                    Callback::from(move |_| {
+                                        save_ticket.emit(String::from("labels"));
+                                    })
+                                }}>{"Save"}</button>
+                            </div>
+                        </>
+                    }
+                } else {
+                    html! {
+                        <>
+                            <div class="card-detail-label-group card-detail-labels">
+                                {if props.ticket.labels.is_empty() {
+                                    html!(<span>{"No labels"}</span>)
+                                } else {
+                                    props.ticket.labels.iter().map(label_chip).collect::<Html>()
+                                }}
+                            </div>
+                            <div class="card-detail-button-group">
+                                <button onclick={{
+                                    let editing_labels = editing_labels.clone();
+                                    Callback::from(move |_| {
+                                        editing_labels.set(true);
+                                    })
+                                }}>{"Edit"}</button>
+                            </div>
+                        </>
+                    }
+                }}
+            </div>
+        },
+        _ => html!(),
+    };
+
+    html! {
+        <Modal close={props.close.clone()}>
+            <StyledCardEditModal>
+                {form_fields(context.team(), &context.fields).into_iter().map(|field| match field {
+                    FormField::Builtin(builtin) => builtin_row(builtin.key),
+                    FormField::Custom(field) => html! {
+                        <CustomFieldRow field={field.clone()} ticket={props.ticket.clone()} />
+                    },
+                }).collect::<Html>()}
+                <Watchers ticket={props.ticket.clone()} />
+                <Checklist ticket={props.ticket.clone()} />
+                <Subtasks ticket={props.ticket.clone()} />
+                <TicketLinks ticket={props.ticket.clone()} />
+                <div class="card-detail-meta">
+                    <div>
+                        {format!("Reported by {}", match props.ticket.reporter.clone() {
+                            Some(reporter) => reporter.name,
+                            None => String::from("unknown"),
+                        })}
+                    </div>
+                    {[("Created", &props.ticket.created_at), ("Updated", &props.ticket.updated_at)]
+                        .iter()
+                        .filter_map(|(label, value)| {
+                            let value = value.as_deref().and_then(local_datetime)?;
+                            Some(html!(<div>{format!("{} {}", label, value)}</div>))
+                        })
+                        .collect::<Html>()}
                 </div>
-                <div class="card-detail-group">
-                    <span>{"Status: "}</span>
-                    {if *editing_status {
+                <AttachmentPanel ticket_id={props.ticket.id.clone()} />
+                <CommentPanel ticket_id={props.ticket.id.clone()} />
+                <ActivityLog ticket_id={props.ticket.id.clone()} />
+                <div class="card-detail-action-group">
+                    {if context.teams.len() > 1 {
                         html! {
-                            <>
-                                <div class="card-detail-label-group">
-                                    <select
-                                        value={(*updated_status).clone()}
-                                        onchange={{
-                                            let updated_status = updated_status.clone();
-                                            Callback::from(move |e: Event| {
-                                                updated_status.set(
-                                                    e.target_dyn_into::<HtmlSelectElement>()
-                                                        .unwrap()
-                                                        .value()
-                                                );
-                                            })
-                                        }}
-                                    >
-                                        {STATUSES.clone().into_iter().map(|status| {
-                                            html!{
-                                                <option
-                                                    value={status.value.clone()}
-                                                    selected={status.value.clone() == (*updated_status).clone()}
-                                                >{status.label.clone()}</option>
-                                            }
-                                        }).collect::<Html>()}
-                                    </select>
-                                </div>
-                                <div class="card-detail-button-group">
-                                    <button onclick={{
-                                        let editing_status = editing_status.clone();
-                                        Callback::from(move |_| {
-                                            editing_status.set(false);
-                                        })
-                                    }}>{"Cancel"}</button>
-                                    <button onclick={{
-                                        let save_ticket = save_ticket.clone();
-                                        Callback::from(move |_| {
-                                            save_ticket.emit(String::from("status"));
-                                        })
-                                    }}>{"Save"}</button
-                                    >
-                                </div>
-                            </>
+                            <button class="move-team" onclick={open_move_team}>{"Move to team…"}</button>
                         }
                     } else {
+                        html!()
+                    }}
+                    {if archived || props.ticket.status == "Complete" {
                         html! {
-                            <>
-                                <div class="card-detail-label-group">
-                                    <span>{{
-                                        let status = props.ticket.status.clone();
-                                        STATUSES
-                                            .clone()
-                                            .into_iter()
-                                            .find(move |s| s.value == status)
-                                            .unwrap_or(&Status {
-                                                value: "",
-                                                label: "No Status",
-                                            })
-                                            .label
-                                    }}</span>
-                                </div>
-                                <div class="card-detail-button-group">
-                                    <button onclick={{
-                                        let editing_status = editing_status.clone();
-                                        Callback::from(move |_| {
-                                            editing_status.set(true);
-                                        })
-                                    }}>{"Edit"}</button>
-                                </div>
-                            </>
+                            <button class="archive" onclick={toggle_archived}>
+                                {if archived { "Restore from archive" } else { "Archive" }}
+                            </button>
                         }
+                    } else {
+                        html!()
                     }}
-                </div>
-                <div class="card-detail-action-group">
                     <button onclick={delete_ticket}>{"Delete"}</button>
                 </div>
             </StyledCardEditModal>
@@ -0,0 +1,305 @@
+use super::{
+    archive_modal::set_archived,
+    card_edit_modal::{delete_ticket, update_ticket, STATUSES},
+    checklist::status_blocker,
+    watchers::{is_watching, set_watching},
+};
+use crate::{
+    common::{copy_to_clipboard, ticket_url, ComponentProps},
+    AppContext, Ticket, TicketDialog,
+};
+use std::rc::Rc;
+use stylist::yew::styled_component;
+use wasm_bindgen_futures::spawn_local;
+use yew::{function_component, html, use_context, use_state, Callback, Html, Properties};
+use yew_icons::{Icon, IconId};
+
+#[derive(Properties, PartialEq)]
+pub struct CardMenuProps {
+    pub ticket: Rc<Ticket>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum CardMenuView {
+    Actions,
+    MoveTo,
+    SetOwner,
+}
+
+#[styled_component(StyledCardMenu)]
+fn styled_card_menu(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            position: relative;
+            .card-menu-toggle {
+                cursor: pointer;
+                padding: 4px;
+            }
+            .card-menu {
+                position: absolute;
+                top: 24px;
+                right: 0;
+                z-index: 1;
+                min-width: 160px;
+                padding: 4px 0;
+                background-color: #fff;
+                border-radius: 3px;
+                box-shadow: 0 10px 15px -3px rgb(0 0 0 / 0.1),
+                  0 4px 6px -4px rgb(0 0 0 / 0.1);
+                font-family: sans-serif;
+                font-size: 14px;
+                color: #4c4e52;
+            }
+            .card-menu-item {
+                padding: 6px 12px;
+                white-space: nowrap;
+                cursor: pointer;
+            }
+            .card-menu-item:hover {
+                background-color: #f0f0f0;
+            }
+            .card-menu-item.disabled {
+                color: #c0c0c0;
+                cursor: default;
+            }
+            .card-menu-item.danger {
+                color: #f44336;
+            }
+            .card-menu-divider {
+                border-top: 1px solid #e0e0e0;
+                margin: 4px 0;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+#[function_component(CardMenu)]
+pub fn card_menu(props: &CardMenuProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let view = use_state(|| None::<CardMenuView>);
+
+    let show = |next: Option<CardMenuView>| {
+        let view = view.clone();
+        Callback::from(move |_| {
+            view.set(next);
+        })
+    };
+    let toggle_menu = {
+        let view = view.clone();
+        Callback::from(move |_| {
+            view.set(match *view {
+                Some(_) => None,
+                None => Some(CardMenuView::Actions),
+            });
+        })
+    };
+
+    let update = {
+        let id = props.ticket.id.clone();
+        let update_tickets = context.update_tickets.clone();
+        let view = view.clone();
+        Callback::from(move |(key, value): (String, String)| {
+            let id = id.clone();
+            let update_tickets = update_tickets.clone();
+            view.set(None);
+            spawn_local(async move {
+                update_ticket(id, key, value.into()).await;
+                update_tickets.emit(());
+            });
+        })
+    };
+    let assign_to_me = {
+        let update = update.clone();
+        let current_user = context.current_user.clone();
+        Callback::from(move |_| {
+            if let Some(user) = current_user.clone() {
+                update.emit(("owner".into(), user.id));
+            }
+        })
+    };
+    let open_duplicate = {
+        let ticket = props.ticket.clone();
+        let update_ticket_dialog = context.update_ticket_dialog.clone();
+        let view = view.clone();
+        Callback::from(move |_| {
+            view.set(None);
+            update_ticket_dialog.emit(Some(TicketDialog::Duplicate(ticket.clone())));
+        })
+    };
+    let open_move_team = {
+        let ticket = props.ticket.clone();
+        let update_ticket_dialog = context.update_ticket_dialog.clone();
+        let view = view.clone();
+        Callback::from(move |_| {
+            view.set(None);
+            update_ticket_dialog.emit(Some(TicketDialog::MoveTeam(ticket.clone())));
+        })
+    };
+    let watching = match &context.current_user {
+        Some(user) => is_watching(&props.ticket, &user.id),
+        None => false,
+    };
+    let toggle_watch = {
+        let id = props.ticket.id.clone();
+        let update_tickets = context.update_tickets.clone();
+        let view = view.clone();
+        Callback::from(move |_| {
+            let id = id.clone();
+            let update_tickets = update_tickets.clone();
+            view.set(None);
+            spawn_local(async move {
+                set_watching(id, !watching).await;
+                update_tickets.emit(());
+            });
+        })
+    };
+    let archive = {
+        let id = props.ticket.id.clone();
+        let update_tickets = context.update_tickets.clone();
+        let view = view.clone();
+        Callback::from(move |_| {
+            let id = id.clone();
+            let update_tickets = update_tickets.clone();
+            view.set(None);
+            spawn_local(async move {
+                set_archived(id, true).await;
+                update_tickets.emit(());
+            });
+        })
+    };
+    let copy_link = {
+        let reference = props.ticket.reference().to_string();
+        let team = context.current_team.clone().unwrap_or_default();
+        let view = view.clone();
+        Callback::from(move |_| {
+            copy_to_clipboard(&ticket_url(&team, &reference));
+            view.set(None);
+        })
+    };
+    let delete = {
+        let ticket = props.ticket.clone();
+        let update_tickets = context.update_tickets.clone();
+        let update_deleted_ticket = context.update_deleted_ticket.clone();
+        let view = view.clone();
+        Callback::from(move |_| {
+            let ticket = ticket.clone();
+            let update_tickets = update_tickets.clone();
+            let update_deleted_ticket = update_deleted_ticket.clone();
+            view.set(None);
+            spawn_local(async move {
+                delete_ticket(ticket.id.clone()).await;
+                update_tickets.emit(());
+                update_deleted_ticket.emit(Some(ticket));
+            });
+        })
+    };
+
+    html! {
+        <StyledCardMenu>
+            <div onmouseleave={show(None)}>
+                <span class="card-menu-toggle" onclick={toggle_menu}>
+                    <Icon
+                        icon_id={IconId::FontAwesomeSolidEllipsisVertical}
+                        style={"height: 16px; width: 16px; fill: #808080;"}
+                    />
+                </span>
+                {match *view {
+                    Some(CardMenuView::Actions) => html! {
+                        <div class="card-menu">
+                            <div class="card-menu-item" onclick={show(Some(CardMenuView::MoveTo))}>
+                                {"Move to…"}
+                            </div>
+                            <div class="card-menu-item" onclick={show(Some(CardMenuView::SetOwner))}>
+                                {"Set owner…"}
+                            </div>
+                            {if context.current_user.is_some() {
+                                html! {
+                                    <div class="card-menu-item" onclick={assign_to_me}>
+                                        {"Make me owner"}
+                                    </div>
+                                }
+                            } else {
+                                html!(<div class="card-menu-item disabled">{"Make me owner"}</div>)
+                            }}
+                            {if context.current_user.is_some() {
+                                html! {
+                                    <div class="card-menu-item" onclick={toggle_watch}>
+                                        {if watching { "Unwatch" } else { "Watch" }}
+                                    </div>
+                                }
+                            } else {
+                                html!()
+                            }}
+                            {if context.teams.len() > 1 {
+                                html! {
+                                    <div class="card-menu-item" onclick={open_move_team}>
+                                        {"Move to team…"}
+                                    </div>
+                                }
+                            } else {
+                                html!()
+                            }}
+                            <div class="card-menu-item" onclick={open_duplicate}>{"Duplicate…"}</div>
+                            {if props.ticket.status == "Complete" {
+                                html!(<div class="card-menu-item" onclick={archive}>{"Archive"}</div>)
+                            } else {
+                                html!()
+                            }}
+                            <div class="card-menu-item" onclick={copy_link}>{"Copy link"}</div>
+                            <div class="card-menu-divider" />
+                            <div class="card-menu-item danger" onclick={delete}>{"Delete"}</div>
+                        </div>
+                    },
+                    Some(CardMenuView::MoveTo) => html! {
+                        <div class="card-menu">
+                            {STATUSES.iter().filter(|s| s.value != props.ticket.status).map(|status| {
+                                let update = update.clone();
+                                if let Some(reason) = status_blocker(context.team(), &props.ticket, status.value) {
+                                    return html! {
+                                        <div class="card-menu-item disabled" title={reason}>
+                                            {status.label}
+                                        </div>
+                                    };
+                                }
+                                html! {
+                                    <div
+                                        class="card-menu-item"
+                                        onclick={Callback::from(move |_| {
+                                            update.emit(("status".into(), status.value.into()));
+                                        })}
+                                    >{status.label}</div>
+                                }
+                            }).collect::<Html>()}
+                        </div>
+                    },
+                    Some(CardMenuView::SetOwner) => html! {
+                        <div class="card-menu">
+                            <div
+                                class="card-menu-item"
+                                onclick={{
+                                    let update = update.clone();
+                                    Callback::from(move |_| {
+                                        update.emit(("owner".into(), "".into()));
+                                    })
+                                }}
+                            >{"No owner"}</div>
+                            {context.users.iter().map(|user| {
+                                let update = update.clone();
+                                let id = user.id.clone();
+                                html! {
+                                    <div
+                                        class="card-menu-item"
+                                        onclick={Callback::from(move |_| {
+                                            update.emit(("owner".into(), id.clone()));
+                                        })}
+                                    >{user.name.clone()}</div>
+                                }
+                            }).collect::<Html>()}
+                        </div>
+                    },
+                    None => html!(),
+                }}
+            </div>
+        </StyledCardMenu>
+    }
+}
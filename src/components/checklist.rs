@@ -0,0 +1,339 @@
+use crate::{common::ComponentProps, AppContext, Team, Ticket};
+use gloo_net::http::Request;
+use serde_json::{json, Value};
+use std::rc::Rc;
+use stylist::yew::styled_component;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{Event, HtmlInputElement};
+use yew::{
+    classes, function_component, html, use_context, use_state, Callback, Html, Properties,
+    TargetCast,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct ChecklistProps {
+    pub ticket: Rc<Ticket>,
+}
+
+/// Why `ticket` can't move to `status` under its team's rules, if it can't.
+pub fn status_blocker(team: Option<&Team>, ticket: &Ticket, status: &str) -> Option<String> {
+    if ticket.status == status {
+        return None;
+    }
+    completion_blocker(team, status, checklist_progress(ticket))
+}
+
+/// Why a ticket with `done` of `total` checklist items checked can't be in
+/// `status` under the team's rules, e.g. when it is created or copied.
+pub fn completion_blocker(
+    team: Option<&Team>,
+    status: &str,
+    (done, total): (usize, usize),
+) -> Option<String> {
+    let require_checklist = team.map(|t| t.require_checklist).unwrap_or(false);
+    if require_checklist && status == "Complete" && done < total {
+        return Some(format!(
+            "Check off every checklist item first ({}/{})",
+            done, total
+        ));
+    }
+    None
+}
+
+/// Checked and total checklist items.
+pub fn checklist_progress(ticket: &Ticket) -> (usize, usize) {
+    let done = ticket.checklist.iter().filter(|item| item.checked).count();
+    (done, ticket.checklist.len())
+}
+
+async fn add_checklist_item(ticket_id: String, text: String) {
+    let _response = Request::post(&format!("/api/tickets/{}/checklist/", ticket_id))
+        .json(&json!({ "text": text }))
+        .unwrap()
+        .send()
+        .await;
+}
+
+async fn update_checklist_item(id: String, key: String, value: Value) {
+    let _response = Request::put(&format!("/api/checklist/{}", id))
+        .json(&json!({ key: value }))
+        .unwrap()
+        .send()
+        .await;
+}
+
+async fn delete_checklist_item(id: String) {
+    let _response = Request::delete(&format!("/api/checklist/{}", id))
+        .send()
+        .await;
+}
+
+async fn reorder_checklist(ticket_id: String, ids: Vec<String>) {
+    let _response = Request::put(&format!("/api/tickets/{}/checklist/order", ticket_id))
+        .json(&json!({ "ids": ids }))
+        .unwrap()
+        .send()
+        .await;
+}
+
+#[styled_component(StyledChecklist)]
+fn styled_checklist(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            margin-top: 20px;
+            .checklist-title {
+                display: flex;
+                justify-content: space-between;
+                font-size: 16px;
+                color: #808080;
+            }
+            .checklist-progress {
+                height: 4px;
+                margin-top: 6px;
+                background-color: #e0e0e0;
+                border-radius: 2px;
+            }
+            .checklist-progress > div {
+                height: 100%;
+                background-color: #74be43;
+                border-radius: 2px;
+            }
+            .checklist-item {
+                display: flex;
+                align-items: center;
+                margin-top: 6px;
+            }
+            .checklist-item > span {
+                flex-grow: 1;
+                margin-left: 6px;
+            }
+            .checklist-item.checked > span {
+                color: #808080;
+                text-decoration: line-through;
+            }
+            .checklist-item > button {
+                margin-left: 4px;
+            }
+            .checklist-add {
+                display: flex;
+                margin-top: 8px;
+            }
+            .checklist-add > input {
+                flex-grow: 1;
+                margin-right: 5px;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+#[function_component(Checklist)]
+pub fn checklist(props: &ChecklistProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let new_item = use_state(|| String::from(""));
+    let (done, total) = checklist_progress(&props.ticket);
+
+    let add_item = {
+        let ticket_id = props.ticket.id.clone();
+        let new_item = new_item.clone();
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |_| {
+            let text = (*new_item).trim().to_string();
+            if text.is_empty() {
+                return;
+            }
+            let ticket_id = ticket_id.clone();
+            let update_tickets = update_tickets.clone();
+            new_item.set(String::from(""));
+            spawn_local(async move {
+                add_checklist_item(ticket_id, text).await;
+                update_tickets.emit(());
+            });
+        })
+    };
+    let update_item = {
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |(id, key, value): (String, String, Value)| {
+            let update_tickets = update_tickets.clone();
+            spawn_local(async move {
+                update_checklist_item(id, key, value).await;
+                update_tickets.emit(());
+            });
+        })
+    };
+    let delete_item = {
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |id: String| {
+            let update_tickets = update_tickets.clone();
+            spawn_local(async move {
+                delete_checklist_item(id).await;
+                update_tickets.emit(());
+            });
+        })
+    };
+    let move_item = {
+        let ticket_id = props.ticket.id.clone();
+        let items = props.ticket.checklist.clone();
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |(index, target): (usize, usize)| {
+            if target >= items.len() {
+                return;
+            }
+            let mut ids = items.iter().map(|i| i.id.clone()).collect::<Vec<String>>();
+            ids.swap(index, target);
+            let ticket_id = ticket_id.clone();
+            let update_tickets = update_tickets.clone();
+            spawn_local(async move {
+                reorder_checklist(ticket_id, ids).await;
+                update_tickets.emit(());
+            });
+        })
+    };
+
+    html! {
+        <StyledChecklist>
+            <div class="checklist-title">
+                <span>{"Checklist"}</span>
+                <span>{format!("{}/{}", done, total)}</span>
+            </div>
+            {match (done * 100).checked_div(total) {
+                Some(percent) => html! {
+                    <div class="checklist-progress">
+                        <div style={format!("width: {}%;", percent)} />
+                    </div>
+                },
+                None => html!(),
+            }}
+            {props.ticket.checklist.iter().enumerate().map(|(index, item)| {
+                let toggle = {
+                    let id = item.id.clone();
+                    let checked = item.checked;
+                    let update_item = update_item.clone();
+                    Callback::from(move |_| {
+                        update_item.emit((id.clone(), "checked".into(), (!checked).into()));
+                    })
+                };
+                let delete = {
+                    let id = item.id.clone();
+                    let delete_item = delete_item.clone();
+                    Callback::from(move |_| {
+                        delete_item.emit(id.clone());
+                    })
+                };
+                let move_to = |target: usize| {
+                    let move_item = move_item.clone();
+                    Callback::from(move |_| {
+                        move_item.emit((index, target));
+                    })
+                };
+                html! {
+                    <div
+                        key={item.id.clone()}
+                        class={classes!("checklist-item", item.checked.then_some("checked"))}
+                    >
+                        <input type="checkbox" checked={item.checked} onchange={toggle} />
+                        <span>{item.text.clone()}</span>
+                        <button disabled={index == 0} onclick={move_to(index.saturating_sub(1))}>
+                            {"↑"}
+                        </button>
+                        <button disabled={index + 1 == total} onclick={move_to(index + 1)}>
+                            {"↓"}
+                        </button>
+                        <button onclick={delete}>{"Delete"}</button>
+                    </div>
+                }
+            }).collect::<Html>()}
+            <div class="checklist-add">
+                <input
+                    type="text"
+                    placeholder="Add an item"
+                    value={(*new_item).clone()}
+                    onchange={{
+                        let new_item = new_item.clone();
+                        Callback::from(move |e: Event| {
+                            new_item.set(e.target_dyn_into::<HtmlInputElement>().unwrap().value());
+                        })
+                    }}
+                />
+                <button onclick={add_item}>{"Add"}</button>
+            </div>
+        </StyledChecklist>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn team(require_checklist: bool) -> Team {
+        serde_json::from_value(json!({
+            "id": "t1",
+            "name": "QE",
+            "require_checklist": require_checklist,
+        }))
+        .unwrap()
+    }
+
+    fn ticket(status: &str, checked: &[bool]) -> Ticket {
+        let checklist = checked
+            .iter()
+            .enumerate()
+            .map(|(i, checked)| json!({"id": i.to_string(), "text": "item", "checked": checked}))
+            .collect::<Vec<Value>>();
+        serde_json::from_value(json!({
+            "id": "1",
+            "title": "Ticket",
+            "description": "",
+            "status": status,
+            "checklist": checklist,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn blocks_completing_with_unchecked_items() {
+        let ticket = ticket("Review", &[true, false]);
+        assert_eq!(
+            status_blocker(Some(&team(true)), &ticket, "Complete").as_deref(),
+            Some("Check off every checklist item first (1/2)")
+        );
+    }
+
+    #[test]
+    fn allows_other_moves_and_teams_without_the_rule() {
+        let ticket = ticket("Review", &[false]);
+        assert_eq!(status_blocker(Some(&team(true)), &ticket, "Open"), None);
+        assert_eq!(
+            status_blocker(Some(&team(false)), &ticket, "Complete"),
+            None
+        );
+        assert_eq!(status_blocker(None, &ticket, "Complete"), None);
+    }
+
+    #[test]
+    fn allows_complete_tickets_and_finished_checklists() {
+        let team = team(true);
+        assert_eq!(
+            status_blocker(Some(&team), &ticket("Complete", &[false]), "Complete"),
+            None
+        );
+        assert_eq!(
+            status_blocker(Some(&team), &ticket("Review", &[true, true]), "Complete"),
+            None
+        );
+        assert_eq!(
+            status_blocker(Some(&team), &ticket("Review", &[]), "Complete"),
+            None
+        );
+    }
+
+    #[test]
+    fn blocks_new_complete_tickets_with_unchecked_items() {
+        let team = team(true);
+        assert_eq!(
+            completion_blocker(Some(&team), "Complete", (0, 2)).as_deref(),
+            Some("Check off every checklist item first (0/2)")
+        );
+        assert_eq!(completion_blocker(Some(&team), "Complete", (0, 0)), None);
+        assert_eq!(completion_blocker(Some(&team), "Open", (0, 2)), None);
+    }
+}
@@ -0,0 +1,385 @@
+use crate::{common::ComponentProps, time::local_datetime, AppContext, Comment};
+use gloo_net::http::Request;
+use serde_json::json;
+use std::rc::Rc;
+use stylist::yew::styled_component;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{Event, HtmlTextAreaElement};
+use yew::{
+    function_component, html, use_context, use_effect_with_deps, use_state, Callback, Html,
+    Properties, TargetCast, UseStateHandle,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct CommentPanelProps {
+    pub ticket_id: String,
+}
+
+#[derive(Properties, PartialEq)]
+struct CommentItemProps {
+    comment: Comment,
+    /// Every comment on the ticket, used to find this comment's replies.
+    comments: Rc<Vec<Comment>>,
+    ticket_id: String,
+    reload: Callback<()>,
+}
+
+#[derive(Properties, PartialEq)]
+struct CommentFormProps {
+    #[prop_or_default]
+    value: String,
+    submit_label: &'static str,
+    submit: Callback<String>,
+    #[prop_or_default]
+    cancel: Option<Callback<()>>,
+}
+
+pub async fn fetch_comments(ticket_id: String) -> Vec<Comment> {
+    let response = Request::get(&format!("/api/tickets/{}/comments/", ticket_id))
+        .send()
+        .await;
+    match response {
+        Ok(r) => r.json::<Vec<Comment>>().await.unwrap_or_default(),
+        Err(_) => vec![],
+    }
+}
+
+async fn create_comment(ticket_id: String, body: String, parent_id: Option<String>) {
+    let _response = Request::post(&format!("/api/tickets/{}/comments/", ticket_id))
+        .json(&json!({
+            "body": body,
+            "parent_id": parent_id,
+        }))
+        .unwrap()
+        .send()
+        .await;
+}
+
+async fn update_comment(id: String, body: String) {
+    let _response = Request::put(&format!("/api/comments/{}", id))
+        .json(&json!({ "body": body }))
+        .unwrap()
+        .send()
+        .await;
+}
+
+async fn delete_comment(id: String) {
+    let _response = Request::delete(&format!("/api/comments/{}", id))
+        .send()
+        .await;
+}
+
+/// Top-level comments, plus replies whose parent has been deleted.
+fn is_root(comment: &Comment, comments: &[Comment]) -> bool {
+    match &comment.parent_id {
+        Some(parent_id) => !comments.iter().any(|c| &c.id == parent_id),
+        None => true,
+    }
+}
+
+#[styled_component(StyledCommentPanel)]
+fn styled_comment_panel(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            margin-top: 20px;
+            .comment-panel-title {
+                font-size: 16px;
+                color: #808080;
+            }
+            .comment {
+                margin-top: 12px;
+            }
+            .comment-replies {
+                margin-left: 20px;
+                padding-left: 10px;
+                border-left: 2px solid #e0e0e0;
+            }
+            .comment-meta {
+                font-size: 12px;
+                color: #808080;
+            }
+            .comment-author {
+                font-weight: bold;
+                color: #4c4e52;
+                margin-right: 6px;
+            }
+            .comment-body {
+                margin-top: 4px;
+                white-space: pre-wrap;
+            }
+            .comment-actions > span {
+                font-size: 12px;
+                color: #808080;
+                cursor: pointer;
+                margin-right: 8px;
+            }
+            .comment-form {
+                display: flex;
+                flex-direction: column;
+                margin-top: 8px;
+            }
+            .comment-form textarea {
+                height: 50px;
+                font-family: sans-serif;
+                resize: vertical;
+            }
+            .comment-form-buttons {
+                display: flex;
+                justify-content: end;
+                margin-top: 4px;
+            }
+            .comment-form-buttons > button {
+                margin-left: 5px;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+#[function_component(CommentForm)]
+fn comment_form(props: &CommentFormProps) -> Html {
+    let body = use_state(|| props.value.clone());
+
+    let submit = {
+        let body = body.clone();
+        let submit = props.submit.clone();
+        Callback::from(move |_| {
+            let text = (*body).trim().to_string();
+            if text.is_empty() {
+                return;
+            }
+            body.set(String::from(""));
+            submit.emit(text);
+        })
+    };
+
+    html! {
+        <div class="comment-form">
+            <textarea
+                value={(*body).clone()}
+                onchange={{
+                    let body = body.clone();
+                    Callback::from(move |e: Event| {
+                        body.set(e.target_dyn_into::<HtmlTextAreaElement>().unwrap().value());
+                    })
+                }}
+            />
+            <div class="comment-form-buttons">
+                {match props.cancel.clone() {
+                    Some(cancel) => html! {
+                        <button onclick={cancel.reform(|_| ())}>{"Cancel"}</button>
+                    },
+                    None => html!(),
+                }}
+                <button onclick={submit}>{props.submit_label}</button>
+            </div>
+        </div>
+    }
+}
+
+#[function_component(CommentItem)]
+fn comment_item(props: &CommentItemProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let editing = use_state(|| false);
+    let replying = use_state(|| false);
+    let comment = &props.comment;
+    let is_author = match (&context.current_user, &comment.author) {
+        (Some(user), Some(author)) => user.id == author.id,
+        _ => false,
+    };
+
+    let toggle = |state: &UseStateHandle<bool>, value: bool| {
+        let state = state.clone();
+        Callback::from(move |_| {
+            state.set(value);
+        })
+    };
+    let save = {
+        let id = comment.id.clone();
+        let editing = editing.clone();
+        let reload = props.reload.clone();
+        Callback::from(move |body: String| {
+            let id = id.clone();
+            let editing = editing.clone();
+            let reload = reload.clone();
+            spawn_local(async move {
+                update_comment(id, body).await;
+                editing.set(false);
+                reload.emit(());
+            });
+        })
+    };
+    let reply = {
+        let ticket_id = props.ticket_id.clone();
+        let parent_id = comment.id.clone();
+        let replying = replying.clone();
+        let reload = props.reload.clone();
+        Callback::from(move |body: String| {
+            let ticket_id = ticket_id.clone();
+            let parent_id = parent_id.clone();
+            let replying = replying.clone();
+            let reload = reload.clone();
+            spawn_local(async move {
+                create_comment(ticket_id, body, Some(parent_id)).await;
+                replying.set(false);
+                reload.emit(());
+            });
+        })
+    };
+    let delete = {
+        let id = comment.id.clone();
+        let reload = props.reload.clone();
+        Callback::from(move |_| {
+            let id = id.clone();
+            let reload = reload.clone();
+            spawn_local(async move {
+                delete_comment(id).await;
+                reload.emit(());
+            });
+        })
+    };
+
+    let replies = props
+        .comments
+        .iter()
+        .filter(|c| c.parent_id.as_ref() == Some(&comment.id))
+        .collect::<Vec<&Comment>>();
+
+    html! {
+        <div class="comment">
+            <div class="comment-meta">
+                <span class="comment-author">
+                    {match comment.author.clone() {
+                        Some(author) => author.name,
+                        None => String::from("Unknown"),
+                    }}
+                </span>
+                {comment.created_at.as_deref().and_then(local_datetime).unwrap_or_default()}
+                {if comment.updated_at.is_some() && comment.updated_at != comment.created_at {
+                    " (edited)"
+                } else {
+                    ""
+                }}
+            </div>
+            {if *editing {
+                html! {
+                    <CommentForm
+                        value={comment.body.clone()}
+                        submit_label="Save"
+                        submit={save}
+                        cancel={toggle(&editing, false)}
+                    />
+                }
+            } else {
+                html! {
+                    <>
+                        <div class="comment-body">{comment.body.clone()}</div>
+                        <div class="comment-actions">
+                            <span onclick={toggle(&replying, true).reform(|_| ())}>{"Reply"}</span>
+                            {if is_author {
+                                html! {
+                                    <>
+                                        <span onclick={toggle(&editing, true).reform(|_| ())}>{"Edit"}</span>
+                                        <span onclick={delete}>{"Delete"}</span>
+                                    </>
+                                }
+                            } else {
+                                html!()
+                            }}
+                        </div>
+                    </>
+                }
+            }}
+            <div class="comment-replies">
+                {replies.into_iter().map(|reply| {
+                    html! {
+                        <CommentItem
+                            key={reply.id.clone()}
+                            comment={reply.clone()}
+                            comments={props.comments.clone()}
+                            ticket_id={props.ticket_id.clone()}
+                            reload={props.reload.clone()}
+                        />
+                    }
+                }).collect::<Html>()}
+                {if *replying {
+                    html! {
+                        <CommentForm
+                            submit_label="Reply"
+                            submit={reply}
+                            cancel={toggle(&replying, false)}
+                        />
+                    }
+                } else {
+                    html!()
+                }}
+            </div>
+        </div>
+    }
+}
+
+#[function_component(CommentPanel)]
+pub fn comment_panel(props: &CommentPanelProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let comments = use_state(|| Rc::new(Vec::<Comment>::new()));
+
+    let reload = {
+        let ticket_id = props.ticket_id.clone();
+        let comments = comments.clone();
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |_| {
+            let ticket_id = ticket_id.clone();
+            let comments = comments.clone();
+            let update_tickets = update_tickets.clone();
+            spawn_local(async move {
+                comments.set(Rc::new(fetch_comments(ticket_id).await));
+                // Keeps the comment count on the card in step.
+                update_tickets.emit(());
+            });
+        })
+    };
+    {
+        let comments = comments.clone();
+        use_effect_with_deps(
+            move |ticket_id: &String| {
+                let ticket_id = ticket_id.clone();
+                spawn_local(async move {
+                    comments.set(Rc::new(fetch_comments(ticket_id).await));
+                });
+                || ()
+            },
+            props.ticket_id.clone(),
+        );
+    }
+    let add_comment = {
+        let ticket_id = props.ticket_id.clone();
+        let reload = reload.clone();
+        Callback::from(move |body: String| {
+            let ticket_id = ticket_id.clone();
+            let reload = reload.clone();
+            spawn_local(async move {
+                create_comment(ticket_id, body, None).await;
+                reload.emit(());
+            });
+        })
+    };
+
+    html! {
+        <StyledCommentPanel>
+            <div class="comment-panel-title">
+                {format!("Comments ({})", comments.len())}
+            </div>
+            {comments.iter().filter(|c| is_root(c, &comments)).map(|comment| {
+                html! {
+                    <CommentItem
+                        key={comment.id.clone()}
+                        comment={comment.clone()}
+                        comments={(*comments).clone()}
+                        ticket_id={props.ticket_id.clone()}
+                        reload={reload.clone()}
+                    />
+                }
+            }).collect::<Html>()}
+            <CommentForm submit_label="Comment" submit={add_comment} />
+        </StyledCommentPanel>
+    }
+}
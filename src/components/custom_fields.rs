@@ -0,0 +1,694 @@
+use super::card_edit_modal::update_ticket;
+use crate::{markdown::is_safe_url, AppContext, CustomField, Team, Ticket};
+use gloo_net::http::Request;
+use serde_json::{json, Value};
+use std::rc::Rc;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{Event, HtmlInputElement, HtmlSelectElement};
+use yew::{
+    function_component, html, use_context, use_state, Callback, Html, Properties, TargetCast,
+};
+
+pub struct FieldKind {
+    pub value: &'static str,
+    pub label: &'static str,
+}
+
+pub const FIELD_KINDS: &[FieldKind] = &[
+    FieldKind {
+        value: "text",
+        label: "Text",
+    },
+    FieldKind {
+        value: "number",
+        label: "Number",
+    },
+    FieldKind {
+        value: "select",
+        label: "Select",
+    },
+    FieldKind {
+        value: "multi_select",
+        label: "Multi-select",
+    },
+    FieldKind {
+        value: "date",
+        label: "Date",
+    },
+    FieldKind {
+        value: "url",
+        label: "URL",
+    },
+];
+
+/// A field every ticket has, laid out in the ticket forms together with the
+/// team's custom fields.
+#[derive(Debug, PartialEq)]
+pub struct BuiltinField {
+    pub key: &'static str,
+    pub label: &'static str,
+}
+
+pub const BUILTIN_FIELDS: &[BuiltinField] = &[
+    BuiltinField {
+        key: "title",
+        label: "Title",
+    },
+    BuiltinField {
+        key: "description",
+        label: "Description",
+    },
+    BuiltinField {
+        key: "owner",
+        label: "Owner",
+    },
+    BuiltinField {
+        key: "reviewers",
+        label: "Reviewers",
+    },
+    BuiltinField {
+        key: "status",
+        label: "Status",
+    },
+    BuiltinField {
+        key: "due_date",
+        label: "Due Date",
+    },
+    BuiltinField {
+        key: "priority",
+        label: "Priority",
+    },
+    BuiltinField {
+        key: "severity",
+        label: "Severity",
+    },
+    BuiltinField {
+        key: "labels",
+        label: "Labels",
+    },
+];
+
+/// One row of a ticket form.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FormField<'a> {
+    Builtin(&'static BuiltinField),
+    Custom(&'a CustomField),
+}
+
+impl FormField<'_> {
+    /// The entry for this row in `Team::layout`.
+    pub fn key(&self) -> &str {
+        match self {
+            FormField::Builtin(field) => field.key,
+            FormField::Custom(field) => &field.id,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            FormField::Builtin(field) => field.label,
+            FormField::Custom(field) => &field.name,
+        }
+    }
+}
+
+/// The rows of the team's ticket forms, in order. Built-in fields left out of
+/// the layout are hidden, except the title; custom fields left out of it go
+/// last, so newly added fields show up without touching the layout.
+pub fn form_fields<'a>(team: Option<&Team>, fields: &'a [CustomField]) -> Vec<FormField<'a>> {
+    let layout = team.map(|t| t.layout.as_slice()).unwrap_or_default();
+    if layout.is_empty() {
+        return BUILTIN_FIELDS
+            .iter()
+            .map(FormField::Builtin)
+            .chain(fields.iter().map(FormField::Custom))
+            .collect();
+    }
+
+    let mut form = layout
+        .iter()
+        .filter_map(|key| {
+            BUILTIN_FIELDS
+                .iter()
+                .find(|field| field.key == key)
+                .map(FormField::Builtin)
+                .or_else(|| {
+                    fields
+                        .iter()
+                        .find(|field| &field.id == key)
+                        .map(FormField::Custom)
+                })
+        })
+        .collect::<Vec<FormField>>();
+    if !layout.iter().any(|key| key == "title") {
+        form.insert(0, FormField::Builtin(&BUILTIN_FIELDS[0]));
+    }
+    form.extend(
+        fields
+            .iter()
+            .filter(|field| !layout.contains(&field.id))
+            .map(FormField::Custom),
+    );
+    form
+}
+
+#[derive(Properties, PartialEq)]
+pub struct CustomFieldInputProps {
+    pub field: CustomField,
+    pub value: Value,
+    pub onchange: Callback<Value>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct CustomFieldRowProps {
+    pub field: CustomField,
+    pub ticket: Rc<Ticket>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct FormLayoutSettingsProps {
+    /// Emits the new `Team::layout`.
+    pub onchange: Callback<Vec<String>>,
+}
+
+#[derive(Properties, PartialEq)]
+struct FieldSettingsRowProps {
+    field: CustomField,
+}
+
+async fn create_field(team_id: String, field: Value) {
+    let _response = Request::post(&format!("/api/teams/{}/fields/", team_id))
+        .json(&field)
+        .unwrap()
+        .send()
+        .await;
+}
+
+async fn update_field(id: String, field: Value) {
+    let _response = Request::put(&format!("/api/fields/{}", id))
+        .json(&field)
+        .unwrap()
+        .send()
+        .await;
+}
+
+async fn delete_field(id: String) {
+    let _response = Request::delete(&format!("/api/fields/{}", id)).send().await;
+}
+
+/// Comma separated select options as typed in the settings form.
+fn parse_options(options: &str) -> Vec<String> {
+    options
+        .split(',')
+        .map(|option| option.trim().to_string())
+        .filter(|option| !option.is_empty())
+        .collect()
+}
+
+/// Whether a field has no value worth showing.
+fn is_blank(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.is_empty(),
+        Value::Array(values) => values.is_empty(),
+        _ => false,
+    }
+}
+
+/// Renders a stored value for display, linking URLs with a safe scheme.
+pub fn display_value(field: &CustomField, value: &Value) -> Html {
+    if is_blank(value) {
+        return html!(<span>{"None"}</span>);
+    }
+    match (field.kind.as_str(), value) {
+        ("url", Value::String(url)) if is_safe_url(url) => html! {
+            <a href={url.clone()} target="_blank" rel="noopener noreferrer">{url.clone()}</a>
+        },
+        (_, Value::String(s)) => html!(<span>{s.clone()}</span>),
+        (_, Value::Array(values)) => html! {
+            <span>
+                {values
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<&str>>()
+                    .join(", ")}
+            </span>
+        },
+        _ => html!(<span>{value.to_string()}</span>),
+    }
+}
+
+/// The form control for one custom field, chosen by its kind.
+#[function_component(CustomFieldInput)]
+pub fn custom_field_input(props: &CustomFieldInputProps) -> Html {
+    let text = match &props.value {
+        Value::Number(number) => number.to_string(),
+        value => value.as_str().unwrap_or_default().to_string(),
+    };
+    let input = |kind: &'static str| {
+        let onchange = props.onchange.clone();
+        let number = kind == "number";
+        html! {
+            <input
+                type={kind}
+                value={text.clone()}
+                onchange={Callback::from(move |e: Event| {
+                    let value = e.target_dyn_into::<HtmlInputElement>().unwrap().value();
+                    onchange.emit(if value.is_empty() {
+                        Value::Null
+                    } else if number {
+                        value.parse::<f64>().map(Value::from).unwrap_or(Value::Null)
+                    } else {
+                        Value::from(value)
+                    });
+                })}
+            />
+        }
+    };
+
+    match props.field.kind.as_str() {
+        "number" => input("number"),
+        "date" => input("date"),
+        "url" => input("url"),
+        "select" => {
+            let onchange = props.onchange.clone();
+            html! {
+                <select onchange={Callback::from(move |e: Event| {
+                    let value = e.target_dyn_into::<HtmlSelectElement>().unwrap().value();
+                    onchange.emit(if value.is_empty() { Value::Null } else { Value::from(value) });
+                })}>
+                    <option value="" selected={text.is_empty()}>{"None"}</option>
+                    {props.field.options.iter().map(|option| {
+                        html! {
+                            <option value={option.clone()} selected={option == &text}>
+                                {option.clone()}
+                            </option>
+                        }
+                    }).collect::<Html>()}
+                </select>
+            }
+        }
+        "multi_select" => {
+            let selected = props
+                .value
+                .as_array()
+                .map(|values| {
+                    values
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .map(String::from)
+                        .collect::<Vec<String>>()
+                })
+                .unwrap_or_default();
+            html! {
+                <span class="custom-field-options">
+                    {props.field.options.iter().map(|option| {
+                        let checked = selected.contains(option);
+                        let toggle = {
+                            let option = option.clone();
+                            let selected = selected.clone();
+                            let onchange = props.onchange.clone();
+                            Callback::from(move |_| {
+                                let mut updated = selected.clone();
+                                if checked {
+                                    updated.retain(|o| o != &option);
+                                } else {
+                                    updated.push(option.clone());
+                                }
+                                onchange.emit(json!(updated));
+                            })
+                        };
+                        html! {
+                            <label>
+                                <input type="checkbox" checked={checked} onchange={toggle} />
+                                {option.clone()}
+                            </label>
+                        }
+                    }).collect::<Html>()}
+                </span>
+            }
+        }
+        _ => input("text"),
+    }
+}
+
+/// An edit-in-place row for one custom field in the ticket modal.
+#[function_component(CustomFieldRow)]
+pub fn custom_field_row(props: &CustomFieldRowProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let value = props
+        .ticket
+        .custom_fields
+        .get(&props.field.id)
+        .cloned()
+        .unwrap_or(Value::Null);
+    let editing = use_state(|| false);
+    let updated = use_state(|| value.clone());
+
+    let save = {
+        let id = props.ticket.id.clone();
+        let key = props.field.id.clone();
+        let editing = editing.clone();
+        let updated = updated.clone();
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |_| {
+            let id = id.clone();
+            let editing = editing.clone();
+            let update_tickets = update_tickets.clone();
+            // Only the changed field is sent; the backend merges it in.
+            let value = json!({ key.clone(): (*updated).clone() });
+            spawn_local(async move {
+                update_ticket(id, "custom_fields".into(), value).await;
+                editing.set(false);
+                update_tickets.emit(());
+            });
+        })
+    };
+    let start_editing = {
+        let editing = editing.clone();
+        let updated = updated.clone();
+        let value = value.clone();
+        Callback::from(move |_| {
+            updated.set(value.clone());
+            editing.set(true);
+        })
+    };
+
+    html! {
+        <div class="card-detail-group">
+            <span>{format!("{}: ", props.field.name)}</span>
+            {if *editing {
+                html! {
+                    <>
+                        <div class="card-detail-label-group">
+                            <CustomFieldInput
+                                field={props.field.clone()}
+                                value={(*updated).clone()}
+                                onchange={{
+                                    let updated = updated.clone();
+                                    Callback::from(move |value: Value| {
+                                        updated.set(value);
+                                    })
+                                }}
+                            />
+                        </div>
+                        <div class="card-detail-button-group">
+                            <button onclick={{
+                                let editing = editing.clone();
+                                Callback::from(move |_| {
+                                    editing.set(false);
+                                })
+                            }}>{"Cancel"}</button>
+                            <button onclick={save}>{"Save"}</button>
+                        </div>
+                    </>
+                }
+            } else {
+                html! {
+                    <>
+                        <div class="card-detail-label-group">
+                            {display_value(&props.field, &value)}
+                        </div>
+                        <div class="card-detail-button-group">
+                            <button onclick={start_editing}>{"Edit"}</button>
+                        </div>
+                    </>
+                }
+            }}
+        </div>
+    }
+}
+
+fn kind_options(selected: &str) -> Html {
+    FIELD_KINDS
+        .iter()
+        .map(|kind| {
+            html! {
+                <option value={kind.value} selected={kind.value == selected}>{kind.label}</option>
+            }
+        })
+        .collect::<Html>()
+}
+
+fn field_json(name: &str, kind: &str, options: &str) -> Value {
+    json!({
+        "name": name,
+        "kind": kind,
+        "options": parse_options(options),
+    })
+}
+
+#[function_component(FieldSettingsRow)]
+fn field_settings_row(props: &FieldSettingsRowProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let name = use_state(|| props.field.name.clone());
+    let kind = use_state(|| props.field.kind.clone());
+    let options = use_state(|| props.field.options.join(", "));
+
+    let save = {
+        let id = props.field.id.clone();
+        let name = name.clone();
+        let kind = kind.clone();
+        let options = options.clone();
+        let update_fields = context.update_fields.clone();
+        Callback::from(move |_| {
+            let id = id.clone();
+            let field = field_json(&name, &kind, &options);
+            let update_fields = update_fields.clone();
+            spawn_local(async move {
+                update_field(id, field).await;
+                update_fields.emit(());
+            });
+        })
+    };
+    let delete = {
+        let id = props.field.id.clone();
+        let update_fields = context.update_fields.clone();
+        Callback::from(move |_| {
+            let id = id.clone();
+            let update_fields = update_fields.clone();
+            spawn_local(async move {
+                delete_field(id).await;
+                update_fields.emit(());
+            });
+        })
+    };
+
+    html! {
+        <div class="team-settings-row">
+            <input
+                type="text"
+                value={(*name).clone()}
+                onchange={{
+                    let name = name.clone();
+                    Callback::from(move |e: Event| {
+                        name.set(e.target_dyn_into::<HtmlInputElement>().unwrap().value());
+                    })
+                }}
+            />
+            <select onchange={{
+                let kind = kind.clone();
+                Callback::from(move |e: Event| {
+                    kind.set(e.target_dyn_into::<HtmlSelectElement>().unwrap().value());
+                })
+            }}>
+                {kind_options(&kind)}
+            </select>
+            {if kind.ends_with("select") {
+                html! {
+                    <input
+                        type="text"
+                        placeholder="Options, comma separated"
+                        value={(*options).clone()}
+                        onchange={{
+                            let options = options.clone();
+                            Callback::from(move |e: Event| {
+                                options.set(e.target_dyn_into::<HtmlInputElement>().unwrap().value());
+                            })
+                        }}
+                    />
+                }
+            } else {
+                html!()
+            }}
+            <button onclick={save}>{"Save"}</button>
+            <button onclick={delete}>{"Delete"}</button>
+        </div>
+    }
+}
+
+/// The custom field section of the team settings modal.
+#[function_component(CustomFieldSettings)]
+pub fn custom_field_settings() -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let name = use_state(|| String::from(""));
+    let kind = use_state(|| String::from(FIELD_KINDS[0].value));
+    let options = use_state(|| String::from(""));
+
+    let add_field = {
+        let team_id = context.current_team_id();
+        let name = name.clone();
+        let kind = kind.clone();
+        let options = options.clone();
+        let update_fields = context.update_fields.clone();
+        Callback::from(move |_| {
+            if name.trim().is_empty() {
+                return;
+            }
+            let team_id = team_id.clone();
+            let field = field_json(name.trim(), &kind, &options);
+            let update_fields = update_fields.clone();
+            name.set(String::from(""));
+            options.set(String::from(""));
+            spawn_local(async move {
+                create_field(team_id, field).await;
+                update_fields.emit(());
+            });
+        })
+    };
+
+    html! {
+        <div class="team-settings-section">
+            <div class="team-settings-section-title">{"Custom Fields"}</div>
+            {context.fields.iter().map(|field| {
+                html! {<FieldSettingsRow key={field.id.clone()} field={field.clone()} />}
+            }).collect::<Html>()}
+            <div class="team-settings-row">
+                <input
+                    type="text"
+                    placeholder="New field"
+                    value={(*name).clone()}
+                    onchange={{
+                        let name = name.clone();
+                        Callback::from(move |e: Event| {
+                            name.set(e.target_dyn_into::<HtmlInputElement>().unwrap().value());
+                        })
+                    }}
+                />
+                <select onchange={{
+                    let kind = kind.clone();
+                    Callback::from(move |e: Event| {
+                        kind.set(e.target_dyn_into::<HtmlSelectElement>().unwrap().value());
+                    })
+                }}>
+                    {kind_options(&kind)}
+                </select>
+                {if kind.ends_with("select") {
+                    html! {
+                        <input
+                            type="text"
+                            placeholder="Options, comma separated"
+                            value={(*options).clone()}
+                            onchange={{
+                                let options = options.clone();
+                                Callback::from(move |e: Event| {
+                                    options.set(e.target_dyn_into::<HtmlInputElement>().unwrap().value());
+                                })
+                            }}
+                        />
+                    }
+                } else {
+                    html!()
+                }}
+                <button onclick={add_field}>{"Add"}</button>
+            </div>
+        </div>
+    }
+}
+
+/// The ticket form section of the team settings modal: reorders the form rows
+/// and hides or shows built-in fields.
+#[function_component(FormLayoutSettings)]
+pub fn form_layout_settings(props: &FormLayoutSettingsProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let form = form_fields(context.team(), &context.fields);
+    let keys = form
+        .iter()
+        .map(|field| field.key().to_string())
+        .collect::<Vec<String>>();
+    let update = |layout: Vec<String>| {
+        let onchange = props.onchange.clone();
+        Callback::from(move |_| onchange.emit(layout.clone()))
+    };
+
+    html! {
+        <div class="team-settings-section">
+            <div class="team-settings-section-title">{"Ticket Form"}</div>
+            {form.iter().enumerate().map(|(index, field)| {
+                let move_to = |target: usize| {
+                    let mut layout = keys.clone();
+                    if target < layout.len() {
+                        layout.swap(index, target);
+                    }
+                    update(layout)
+                };
+                let hidden = keys.iter().filter(|key| *key != field.key()).cloned().collect();
+                let hideable = matches!(field, FormField::Builtin(builtin) if builtin.key != "title");
+                html! {
+                    <div class="team-settings-row">
+                        <span class="team-settings-name">{field.label()}</span>
+                        <button disabled={index == 0} onclick={move_to(index.saturating_sub(1))}>{"↑"}</button>
+                        <button disabled={index + 1 == keys.len()} onclick={move_to(index + 1)}>{"↓"}</button>
+                        <button disabled={!hideable} onclick={update(hidden)}>{"Hide"}</button>
+                    </div>
+                }
+            }).collect::<Html>()}
+            {BUILTIN_FIELDS.iter().filter(|field| !keys.iter().any(|key| key == field.key)).map(|field| {
+                let mut shown = keys.clone();
+                shown.push(field.key.to_string());
+                html! {
+                    <div class="team-settings-row">
+                        <span class="team-settings-name hidden-field">{field.label}</span>
+                        <button onclick={update(shown)}>{"Show"}</button>
+                    </div>
+                }
+            }).collect::<Html>()}
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn team(layout: &[&str]) -> Team {
+        serde_json::from_value(json!({"id": "t1", "name": "QE", "layout": layout})).unwrap()
+    }
+
+    fn field(id: &str) -> CustomField {
+        CustomField {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            kind: String::from("text"),
+            options: vec![],
+        }
+    }
+
+    fn keys(form: &[FormField]) -> Vec<String> {
+        form.iter().map(|field| field.key().to_string()).collect()
+    }
+
+    #[test]
+    fn defaults_to_builtin_fields_then_custom_fields() {
+        let fields = vec![field("f1"), field("f2")];
+        let form = form_fields(Some(&team(&[])), &fields);
+        assert_eq!(form.len(), BUILTIN_FIELDS.len() + 2);
+        assert_eq!(form[0], FormField::Builtin(&BUILTIN_FIELDS[0]));
+        assert_eq!(keys(&form[BUILTIN_FIELDS.len()..]), ["f1", "f2"]);
+        assert_eq!(form_fields(None, &fields), form);
+    }
+
+    #[test]
+    fn follows_the_team_layout() {
+        let fields = vec![field("f1"), field("f2")];
+        let form = form_fields(Some(&team(&["f2", "title", "status", "f1"])), &fields);
+        assert_eq!(keys(&form), ["f2", "title", "status", "f1"]);
+    }
+
+    #[test]
+    fn keeps_the_title_and_appends_new_custom_fields() {
+        let fields = vec![field("f1"), field("f2")];
+        let form = form_fields(Some(&team(&["owner", "f1", "gone"])), &fields);
+        assert_eq!(keys(&form), ["title", "owner", "f1", "f2"]);
+    }
+}
@@ -0,0 +1,251 @@
+use super::{card_edit_modal::STATUSES, checklist::completion_blocker, modal::Modal};
+use crate::{common::ComponentProps, AppContext, Ticket};
+use gloo_net::http::Request;
+use serde_json::json;
+use std::rc::Rc;
+use stylist::yew::styled_component;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{Event, HtmlInputElement, HtmlSelectElement};
+use yew::{
+    function_component, html, use_context, use_state, Callback, Html, Properties, TargetCast,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct DuplicateModalProps {
+    pub ticket: Rc<Ticket>,
+    pub close: Callback<()>,
+}
+
+pub struct CopyPart {
+    pub value: &'static str,
+    pub label: &'static str,
+}
+
+/// Parts of a ticket that can be carried over to its copy. The title and
+/// status are always set from the dialog.
+pub const COPY_PARTS: &[CopyPart] = &[
+    CopyPart {
+        value: "description",
+        label: "Description",
+    },
+    CopyPart {
+        value: "people",
+        label: "Owner and reviewers",
+    },
+    CopyPart {
+        value: "levels",
+        label: "Priority and severity",
+    },
+    CopyPart {
+        value: "labels",
+        label: "Labels",
+    },
+    CopyPart {
+        value: "custom_fields",
+        label: "Custom fields",
+    },
+    CopyPart {
+        value: "due_date",
+        label: "Due date",
+    },
+    CopyPart {
+        value: "checklist",
+        label: "Checklist (unchecked)",
+    },
+    CopyPart {
+        value: "links",
+        label: "Linked tickets",
+    },
+    CopyPart {
+        value: "parent",
+        label: "Parent epic",
+    },
+];
+
+async fn duplicate_ticket(id: String, title: String, status: String, parts: Vec<&'static str>) {
+    let _response = Request::post(&format!("/api/tickets/{}/duplicate/", id))
+        .json(&json!({
+            "title": title,
+            "status": status,
+            "copy": parts,
+        }))
+        .unwrap()
+        .send()
+        .await;
+}
+
+#[styled_component(StyledDuplicateModal)]
+fn styled_duplicate_modal(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            display: flex;
+            flex-direction: column;
+            width: 100%;
+            padding: 16px;
+            font-family: sans-serif;
+            font-size: 14px;
+            color: #4c4e52;
+            .duplicate-title {
+                font-size: 22px;
+            }
+            .duplicate-group {
+                display: flex;
+                margin-top: 20px;
+            }
+            .duplicate-group > span {
+                width: 20%;
+            }
+            .duplicate-group > input[type=text] {
+                flex-grow: 1;
+            }
+            .duplicate-parts {
+                display: flex;
+                flex-direction: column;
+                gap: 4px;
+            }
+            .duplicate-parts > label {
+                display: flex;
+                align-items: center;
+            }
+            .duplicate-error {
+                margin-top: 8px;
+                color: #f44336;
+            }
+            .duplicate-action-group {
+                display: flex;
+                justify-content: end;
+                margin-top: 20px;
+            }
+            .duplicate-action-group > button {
+                margin-left: 5px;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+/// Creates a copy of a ticket with a chosen status and a chosen set of
+/// `COPY_PARTS`.
+#[function_component(DuplicateModal)]
+pub fn duplicate_modal(props: &DuplicateModalProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let title = use_state(|| props.ticket.title.clone());
+    let status = use_state(|| String::from(STATUSES[0].value));
+    let parts = use_state(|| {
+        COPY_PARTS
+            .iter()
+            .map(|part| part.value)
+            .collect::<Vec<&'static str>>()
+    });
+
+    // The copied checklist starts unchecked.
+    let copied_items = if parts.contains(&"checklist") {
+        props.ticket.checklist.len()
+    } else {
+        0
+    };
+    let blocker = completion_blocker(context.team(), &status, (0, copied_items));
+
+    let save = {
+        let id = props.ticket.id.clone();
+        let title = title.clone();
+        let status = status.clone();
+        let parts = parts.clone();
+        let update_tickets = context.update_tickets.clone();
+        let close = props.close.clone();
+        Callback::from(move |_| {
+            let id = id.clone();
+            let title = (*title).trim().to_string();
+            if title.is_empty() {
+                return;
+            }
+            let status = (*status).clone();
+            let parts = (*parts).clone();
+            let update_tickets = update_tickets.clone();
+            let close = close.clone();
+            spawn_local(async move {
+                duplicate_ticket(id, title, status, parts).await;
+                update_tickets.emit(());
+                close.emit(());
+            });
+        })
+    };
+
+    html! {
+        <Modal close={props.close.clone()}>
+            <StyledDuplicateModal>
+                <div class="duplicate-title">{"Duplicate ticket"}</div>
+                <div class="duplicate-group">
+                    <span>{"Title:"}</span>
+                    <input
+                        type="text"
+                        value={(*title).clone()}
+                        onchange={{
+                            let title = title.clone();
+                            Callback::from(move |e: Event| {
+                                title.set(e.target_dyn_into::<HtmlInputElement>().unwrap().value());
+                            })
+                        }}
+                    />
+                </div>
+                <div class="duplicate-group">
+                    <span>{"Status:"}</span>
+                    <select
+                        onchange={{
+                            let status = status.clone();
+                            Callback::from(move |e: Event| {
+                                status.set(e.target_dyn_into::<HtmlSelectElement>().unwrap().value());
+                            })
+                        }}
+                    >
+                        {STATUSES.iter().map(|s| {
+                            html! {
+                                <option value={s.value} selected={s.value == *status}>
+                                    {s.label}
+                                </option>
+                            }
+                        }).collect::<Html>()}
+                    </select>
+                </div>
+                <div class="duplicate-group">
+                    <span>{"Copy:"}</span>
+                    <div class="duplicate-parts">
+                        {COPY_PARTS.iter().map(|part| {
+                            let toggle = {
+                                let parts = parts.clone();
+                                let value = part.value;
+                                Callback::from(move |e: Event| {
+                                    let checked =
+                                        e.target_dyn_into::<HtmlInputElement>().unwrap().checked();
+                                    let mut updated = (*parts).clone();
+                                    updated.retain(|p| *p != value);
+                                    if checked {
+                                        updated.push(value);
+                                    }
+                                    parts.set(updated);
+                                })
+                            };
+                            html! {
+                                <label key={part.value}>
+                                    <input
+                                        type="checkbox"
+                                        checked={parts.contains(&part.value)}
+                                        onchange={toggle}
+                                    />
+                                    {part.label}
+                                </label>
+                            }
+                        }).collect::<Html>()}
+                    </div>
+                </div>
+                {match blocker.clone() {
+                    Some(reason) => html!(<div class="duplicate-error">{reason}</div>),
+                    None => html!(),
+                }}
+                <div class="duplicate-action-group">
+                    <button onclick={props.close.reform(|_| ())}>{"Cancel"}</button>
+                    <button disabled={blocker.is_some()} onclick={save}>{"Duplicate"}</button>
+                </div>
+            </StyledDuplicateModal>
+        </Modal>
+    }
+}
@@ -0,0 +1,198 @@
+use super::{
+    card_edit_modal::{Level, PRIORITIES, SEVERITIES},
+    ticket_search::TicketSearch,
+};
+use crate::{common::ComponentProps, AppContext, LinkedTicket, Ticket, TicketFilters};
+use stylist::yew::styled_component;
+use web_sys::{Event, HtmlInputElement, HtmlSelectElement};
+use yew::{function_component, html, use_context, Callback, Html, TargetCast};
+
+#[styled_component(StyledFilterBar)]
+fn styled_filter_bar(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            display: flex;
+            align-items: center;
+            margin-right: 16px;
+            label {
+                display: flex;
+                align-items: center;
+                margin-left: 8px;
+                font-family: sans-serif;
+                font-size: 14px;
+                color: #4c4e52;
+                white-space: nowrap;
+            }
+            .filter-epic {
+                width: 180px;
+                margin-left: 8px;
+            }
+            .filter-epic input {
+                padding: 8px;
+                border: 1px solid #d3d3d3;
+                border-radius: 3px;
+            }
+            .filter-epic-chip {
+                display: flex;
+                align-items: center;
+                margin-left: 8px;
+                padding: 6px 8px;
+                border-radius: 3px;
+                background-color: #e3f2fd;
+                font-family: sans-serif;
+                font-size: 14px;
+                color: #4c4e52;
+                white-space: nowrap;
+            }
+            .filter-epic-chip > button {
+                margin-left: 6px;
+                border: none;
+                background: none;
+                cursor: pointer;
+            }
+            select {
+                margin-left: 8px;
+                padding: 8px;
+                border: 1px solid #d3d3d3;
+                border-radius: 3px;
+                background-color: #fff;
+                color: #4c4e52;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+fn level_options(levels: &[Level], selected: &Option<String>, all: &str) -> Html {
+    html! {
+        <>
+            <option value="" selected={selected.is_none()}>{all.to_string()}</option>
+            {levels.iter().map(|level| {
+                html! {
+                    <option
+                        value={level.value}
+                        selected={selected.as_deref() == Some(level.value)}
+                    >{level.label}</option>
+                }
+            }).collect::<Html>()}
+        </>
+    }
+}
+
+fn selected_value(e: Event) -> Option<String> {
+    let value = e.target_dyn_into::<HtmlSelectElement>().unwrap().value();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+#[function_component(FilterBar)]
+pub fn filter_bar() -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let filters = context.filters.clone();
+
+    let update_priority = {
+        let filters = filters.clone();
+        let update_filters = context.update_filters.clone();
+        Callback::from(move |e: Event| {
+            update_filters.emit(TicketFilters {
+                priority: selected_value(e),
+                ..filters.clone()
+            });
+        })
+    };
+    let update_label = {
+        let filters = filters.clone();
+        let update_filters = context.update_filters.clone();
+        Callback::from(move |e: Event| {
+            update_filters.emit(TicketFilters {
+                label: selected_value(e),
+                ..filters.clone()
+            });
+        })
+    };
+    let update_overdue = {
+        let filters = filters.clone();
+        let update_filters = context.update_filters.clone();
+        Callback::from(move |e: Event| {
+            update_filters.emit(TicketFilters {
+                overdue: e.target_dyn_into::<HtmlInputElement>().unwrap().checked(),
+                ..filters.clone()
+            });
+        })
+    };
+    let update_severity = {
+        let filters = filters.clone();
+        let update_filters = context.update_filters.clone();
+        Callback::from(move |e: Event| {
+            update_filters.emit(TicketFilters {
+                severity: selected_value(e),
+                ..filters.clone()
+            });
+        })
+    };
+
+    let clear_epic = {
+        let filters = filters.clone();
+        let update_filters = context.update_filters.clone();
+        Callback::from(move |_| {
+            update_filters.emit(TicketFilters {
+                epic: None,
+                ..filters.clone()
+            });
+        })
+    };
+    let select_epic = {
+        let filters = filters.clone();
+        let update_filters = context.update_filters.clone();
+        Callback::from(move |ticket: Ticket| {
+            update_filters.emit(TicketFilters {
+                epic: Some(LinkedTicket::from(&ticket)),
+                ..filters.clone()
+            });
+        })
+    };
+
+    html! {
+        <StyledFilterBar>
+            {match &filters.epic {
+                Some(epic) => html! {
+                    <span class="filter-epic-chip" title="Showing this epic's sub-tasks only">
+                        {format!("Epic: {}", epic.name())}
+                        <button onclick={clear_epic}>{"✕"}</button>
+                    </span>
+                },
+                None => html! {
+                    <div class="filter-epic">
+                        <TicketSearch
+                            onselect={select_epic}
+                            placeholder={String::from("Filter by epic...")}
+                        />
+                    </div>
+                },
+            }}
+            <select onchange={update_priority}>
+                {level_options(PRIORITIES, &filters.priority, "All priorities")}
+            </select>
+            <select onchange={update_severity}>
+                {level_options(SEVERITIES, &filters.severity, "All severities")}
+            </select>
+            <select onchange={update_label}>
+                <option value="" selected={filters.label.is_none()}>{"All labels"}</option>
+                {context.labels.iter().map(|label| {
+                    html! {
+                        <option
+                            value={label.id.clone()}
+                            selected={filters.label.as_ref() == Some(&label.id)}
+                        >{label.name.clone()}</option>
+                    }
+                }).collect::<Html>()}
+            </select>
+            <label>
+                <input type="checkbox" checked={filters.overdue} onchange={update_overdue} />
+                {"Overdue"}
+            </label>
+        </StyledFilterBar>
+    }
+}
@@ -1,3 +1,7 @@
+use super::{
+    archive_modal::ArchiveModal, filter_bar::FilterBar, team_settings_modal::TeamSettingsModal,
+    trash_modal::TrashModal,
+};
 use crate::{common::ComponentProps, AppContext};
 use stylist::yew::styled_component;
 use web_sys::{HtmlInputElement, InputEvent, KeyboardEvent};
@@ -5,6 +9,7 @@ use yew::{
     function_component, html, use_context, use_effect_with_deps, use_state, Callback, Html,
     TargetCast,
 };
+use yew_icons::{Icon, IconId};
 
 #[styled_component(StyledHeader)]
 fn styled_header(props: &ComponentProps) -> Html {
@@ -39,6 +44,12 @@ fn styled_header(props: &ComponentProps) -> Html {
                 outline: none;
                 padding: 9px;
             }
+            .header-team-settings, .header-archive, .header-trash {
+                display: flex;
+                align-items: center;
+                margin-left: 12px;
+                cursor: pointer;
+            }
         ")}>{props.children.clone()}</header>
     }
 }
@@ -49,6 +60,9 @@ pub fn header() -> Html {
     let teams = context.teams.clone();
 
     let filtered_teams = use_state(|| vec![]);
+    let show_team_settings = use_state(|| false);
+    let show_trash = use_state(|| false);
+    let show_archive = use_state(|| false);
     {
         let filtered_teams = filtered_teams.clone();
         use_effect_with_deps(
@@ -80,6 +94,44 @@ pub fn header() -> Html {
         })
     };
 
+    let open_team_settings = {
+        let show_team_settings = show_team_settings.clone();
+        Callback::from(move |_| {
+            show_team_settings.set(true);
+        })
+    };
+    let close_team_settings = {
+        let show_team_settings = show_team_settings.clone();
+        Callback::from(move |_| {
+            show_team_settings.set(false);
+        })
+    };
+    let open_trash = {
+        let show_trash = show_trash.clone();
+        Callback::from(move |_| {
+            show_trash.set(true);
+        })
+    };
+    let close_trash = {
+        let show_trash = show_trash.clone();
+        Callback::from(move |_| {
+            show_trash.set(false);
+        })
+    };
+    let open_archive = {
+        let show_archive = show_archive.clone();
+        Callback::from(move |_| {
+            show_archive.set(true);
+        })
+    };
+    let close_archive = {
+        let show_archive = show_archive.clone();
+        Callback::from(move |_| {
+            show_archive.set(false);
+        })
+    };
+    let has_team = context.current_team.is_some();
+
     let submit_team = {
         let teams = teams.clone();
         Callback::from(move |e: KeyboardEvent| {
@@ -101,10 +153,12 @@ pub fn header() -> Html {
                 <span class="header-title">{"QE Manager"}</span>
             </div>
             <div class="header-item">
+                <FilterBar />
                 <div class="header-team-switcher">
                     <input
                         list="teams"
                         placeholder="Select Team"
+                        value={context.current_team.clone().unwrap_or_default()}
                         oninput={filter_teams}
                         onkeypress={submit_team}
                     />
@@ -114,7 +168,48 @@ pub fn header() -> Html {
                         }).collect::<Html>()}
                     </datalist>
                 </div>
+                {if has_team {
+                    html! {
+                        <>
+                            <span class="header-archive" title="Archive" onclick={open_archive}>
+                                <Icon
+                                    icon_id={IconId::FontAwesomeSolidBoxArchive}
+                                    style={"height: 20px; width: 20px; fill: #808080;"}
+                                />
+                            </span>
+                            <span class="header-trash" title="Trash" onclick={open_trash}>
+                                <Icon
+                                    icon_id={IconId::FontAwesomeSolidTrashCan}
+                                    style={"height: 20px; width: 20px; fill: #808080;"}
+                                />
+                            </span>
+                            <span class="header-team-settings" onclick={open_team_settings}>
+                                <Icon
+                                    icon_id={IconId::FontAwesomeSolidGear}
+                                    style={"height: 20px; width: 20px; fill: #808080;"}
+                                />
+                            </span>
+                        </>
+                    }
+                } else {
+                    html!()
+                }}
             </div>
+            {if *show_team_settings {
+                html! {<TeamSettingsModal close={close_team_settings} />}
+            } else {
+                html!()
+            }}
+            {if *show_archive {
+                html! {<ArchiveModal close={close_archive} />}
+            } else {
+                html!()
+            }}
+            {if *show_trash {
+                html! {<TrashModal close={close_trash} />}
+            } else {
+                html!()
+            }}
         </StyledHeader>
     }
 }
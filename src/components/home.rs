@@ -1,7 +1,26 @@
-use super::board::Board;
-use crate::{common::ComponentProps, AppContext, Ticket};
+use super::{
+    board::{sort_tickets, Board, DEFAULT_SORT},
+    card_create_modal::CardCreateModal,
+    card_edit_modal::{fetch_ticket, update_ticket, CardEditModal, STATUSES},
+    checklist::status_blocker,
+    duplicate_modal::DuplicateModal,
+    move_team_modal::MoveTeamModal,
+    shortcut_help_modal::ShortcutHelpModal,
+};
+use crate::{
+    common::{is_typing, ComponentProps},
+    AppContext, OpenTicket, Ticket, TicketDialog,
+};
+use gloo_events::EventListener;
+use std::rc::Rc;
 use stylist::{css, yew::styled_component};
-use yew::{function_component, html, use_context};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::KeyboardEvent;
+use yew::{
+    function_component, html, use_context, use_effect, use_effect_with_deps, use_state, Callback,
+    Html,
+};
 
 #[styled_component(StyledHome)]
 fn styled_home(props: &ComponentProps) -> Html {
@@ -16,37 +35,249 @@ fn styled_home(props: &ComponentProps) -> Html {
     }
 }
 
+fn is_modal_open() -> bool {
+    gloo_utils::document()
+        .query_selector(".modal")
+        .ok()
+        .flatten()
+        .is_some()
+}
+
 #[function_component(Home)]
 pub fn home() -> Html {
     let context = use_context::<AppContext>().expect("no ctx found");
-    let tickets = context.tickets.clone();
-    let new_tickets = tickets
-        .iter()
-        .filter(|t| t.status == "New")
-        .map(|t| t.clone())
-        .collect::<Vec<Ticket>>();
-    let pending_tickets = tickets
-        .iter()
-        .filter(|t| t.status == "Pending")
-        .map(|t| t.clone())
-        .collect::<Vec<Ticket>>();
-    let accepted_tickets = tickets
+    let focus = use_state(|| None::<(usize, usize)>);
+    let create_status = use_state(|| None::<String>);
+    let show_help = use_state(|| false);
+    let fetched_ticket = use_state(|| None::<Rc<Ticket>>);
+
+    let columns = STATUSES
         .iter()
-        .filter(|t| t.status == "Complete")
-        .map(|t| t.clone())
-        .collect::<Vec<Ticket>>();
-    let rejected_tickets = tickets
+        .map(|status| {
+            let column = context
+                .columns
+                .get(status.value)
+                .cloned()
+                .unwrap_or_default();
+            let mut tickets = column.tickets.clone();
+            let sort = context
+                .sorts
+                .get(status.value)
+                .cloned()
+                .unwrap_or(DEFAULT_SORT.into());
+            sort_tickets(&mut tickets, &sort);
+            (column, tickets, sort)
+        })
+        .collect::<Vec<_>>();
+    let tickets = columns
         .iter()
-        .filter(|t| t.status == "Rejected")
-        .map(|t| t.clone())
-        .collect::<Vec<Ticket>>();
+        .map(|(_, tickets, _)| tickets.clone())
+        .collect::<Vec<Vec<Rc<Ticket>>>>();
+    // Cards can move or disappear under the focus, so clamp it to the column.
+    let focused = focus.map(|(col, row)| (col, row.min(tickets[col].len().saturating_sub(1))));
+
+    {
+        let tickets = tickets.clone();
+        let focus = focus.clone();
+        let create_status = create_status.clone();
+        let show_help = show_help.clone();
+        let update_open_ticket = context.update_open_ticket.clone();
+        let update_tickets = context.update_tickets.clone();
+        let team = context.team().cloned();
+        use_effect(move || {
+            let listener = EventListener::new(&gloo_utils::document(), "keydown", move |e| {
+                let e = match e.dyn_ref::<KeyboardEvent>() {
+                    Some(e) => e,
+                    None => return,
+                };
+                if e.ctrl_key() || e.meta_key() || e.alt_key() || is_typing(e) || is_modal_open() {
+                    return;
+                }
+                let key = e.key();
+                let navigating = matches!(
+                    key.as_str(),
+                    "j" | "k" | "h" | "l" | "ArrowDown" | "ArrowUp" | "ArrowLeft" | "ArrowRight"
+                );
+                // The first navigation key only puts focus on the board.
+                if focused.is_none() && navigating {
+                    e.prevent_default();
+                    focus.set(Some((0, 0)));
+                    return;
+                }
+                let (col, row) = focused.unwrap_or((0, 0));
+                let last_row = |col: usize| tickets[col].len().saturating_sub(1);
+                let ticket = tickets[col].get(row).cloned();
+                let open = |field: Option<&str>| {
+                    if let Some(ticket) = ticket.clone() {
+                        update_open_ticket.emit(Some(OpenTicket {
+                            id: ticket.id.clone(),
+                            field: field.map(|f| f.to_string()),
+                        }));
+                    }
+                };
+                let move_status = |offset: isize| {
+                    let ticket = match ticket.clone() {
+                        Some(ticket) => ticket,
+                        None => return,
+                    };
+                    let index = STATUSES
+                        .iter()
+                        .position(|s| s.value == ticket.status)
+                        .unwrap_or(0) as isize;
+                    let status = match STATUSES.get((index + offset) as usize) {
+                        Some(status) if index + offset >= 0 => status,
+                        _ => return,
+                    };
+                    if status_blocker(team.as_ref(), &ticket, status.value).is_some() {
+                        return;
+                    }
+                    let id = ticket.id.clone();
+                    let update_tickets = update_tickets.clone();
+                    spawn_local(async move {
+                        update_ticket(id, "status".into(), status.value.into()).await;
+                        update_tickets.emit(());
+                    });
+                };
+                match key.as_str() {
+                    "j" | "ArrowDown" => focus.set(Some((col, (row + 1).min(last_row(col))))),
+                    "k" | "ArrowUp" => focus.set(Some((col, row.saturating_sub(1)))),
+                    "h" | "ArrowLeft" => {
+                        let col = col.saturating_sub(1);
+                        focus.set(Some((col, row.min(last_row(col)))));
+                    }
+                    "l" | "ArrowRight" => {
+                        let col = (col + 1).min(STATUSES.len() - 1);
+                        focus.set(Some((col, row.min(last_row(col)))));
+                    }
+                    "Enter" => open(None),
+                    "e" => open(Some("title")),
+                    "a" => open(Some("owner")),
+                    "]" => move_status(1),
+                    "[" => move_status(-1),
+                    "c" => create_status.set(Some(STATUSES[col].value.into())),
+                    "?" => show_help.set(true),
+                    _ => return,
+                }
+                e.prevent_default();
+            });
+            move || drop(listener)
+        });
+    }
+
+    let close_edit_modal = {
+        let update_open_ticket = context.update_open_ticket.clone();
+        Callback::from(move |_| {
+            update_open_ticket.emit(None);
+        })
+    };
+    let close_dialog = {
+        let update_ticket_dialog = context.update_ticket_dialog.clone();
+        Callback::from(move |_| {
+            update_ticket_dialog.emit(None);
+        })
+    };
+    let close_create_modal = {
+        let create_status = create_status.clone();
+        Callback::from(move |_| {
+            create_status.set(None);
+        })
+    };
+    let close_help = {
+        let show_help = show_help.clone();
+        Callback::from(move |_| {
+            show_help.set(false);
+        })
+    };
+    let loaded_ticket = context.open_ticket.as_ref().and_then(|open| {
+        tickets
+            .iter()
+            .flatten()
+            .find(|t| t.is_referenced_by(&open.id))
+            .cloned()
+    });
+    // Tickets opened from a link may not be on a loaded page yet.
+    {
+        let fetched_ticket = fetched_ticket.clone();
+        let open_id = context.open_ticket.as_ref().map(|open| open.id.clone());
+        use_effect_with_deps(
+            move |(open_id, loaded, _)| {
+                if let (Some(id), false) = (open_id.clone(), *loaded) {
+                    spawn_local(async move {
+                        fetched_ticket.set(fetch_ticket(id).await.map(Rc::new));
+                    });
+                }
+                || ()
+            },
+            (open_id, loaded_ticket.is_some(), context.revision),
+        );
+    }
+    let open_ticket = context.open_ticket.clone().and_then(|open| {
+        loaded_ticket
+            .or_else(|| {
+                (*fetched_ticket)
+                    .clone()
+                    .filter(|t| t.is_referenced_by(&open.id))
+            })
+            .map(|ticket| (ticket, open.field))
+    });
 
     html! {
         <StyledHome>
-            <Board title={"Ready For Review"} status={"New"} tickets={new_tickets} />
-            <Board title={"In Review"} status={"Pending"} tickets={pending_tickets} />
-            <Board title={"Accepted"} status={"Complete"} tickets={accepted_tickets} />
-            <Board title={"Rejected"} status={"Rejected"} tickets={rejected_tickets} />
+            {columns.into_iter().enumerate().map(|(i, (column, tickets, sort))| {
+                let status = STATUSES[i].value;
+                let open_create_modal = {
+                    let create_status = create_status.clone();
+                    Callback::from(move |_| {
+                        create_status.set(Some(status.into()));
+                    })
+                };
+                html! {
+                    <Board
+                        title={STATUSES[i].label}
+                        status={status}
+                        sort={sort}
+                        tickets={tickets}
+                        total={column.total}
+                        has_more={column.next_cursor.is_some()}
+                        loading={column.loading}
+                        focused={focused.filter(|(col, _)| *col == i).map(|(_, row)| row)}
+                        open_create_modal={open_create_modal}
+                    />
+                }
+            }).collect::<Html>()}
+            {if let Some((ticket, field)) = open_ticket {
+                html! {
+                    <CardEditModal
+                        key={ticket.id.clone()}
+                        ticket={ticket.clone()}
+                        editing={field}
+                        close={close_edit_modal}
+                    />
+                }
+            } else {
+                html!()
+            }}
+            {match context.ticket_dialog.clone() {
+                Some(TicketDialog::Duplicate(ticket)) => html! {
+                    <DuplicateModal {ticket} close={close_dialog} />
+                },
+                Some(TicketDialog::MoveTeam(ticket)) => html! {
+                    <MoveTeamModal {ticket} close={close_dialog} />
+                },
+                None => html!(),
+            }}
+            {if let Some(status) = (*create_status).clone() {
+                html! {
+                    <CardCreateModal status={status} close={close_create_modal} />
+                }
+            } else {
+                html!()
+            }}
+            {if *show_help {
+                html! {<ShortcutHelpModal close={close_help} />}
+            } else {
+                html!()
+            }}
         </StyledHome>
     }
 }
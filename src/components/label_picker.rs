@@ -0,0 +1,88 @@
+use crate::{common::ComponentProps, AppContext, Label};
+use stylist::yew::styled_component;
+use yew::{classes, function_component, html, use_context, Callback, Html, Properties};
+
+#[derive(Properties, PartialEq)]
+pub struct LabelPickerProps {
+    /// Ids of the selected labels.
+    pub selected: Vec<String>,
+    pub onchange: Callback<Vec<String>>,
+}
+
+/// Renders a label as a coloured chip.
+pub fn label_chip(label: &Label) -> Html {
+    html! {
+        <span
+            class="label-chip"
+            style={format!("background-color: {};", label.color)}
+        >{label.name.clone()}</span>
+    }
+}
+
+#[styled_component(StyledLabelPicker)]
+fn styled_label_picker(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            display: flex;
+            flex-wrap: wrap;
+            gap: 4px;
+            .label-option {
+                cursor: pointer;
+                opacity: 0.35;
+            }
+            .label-option.selected {
+                opacity: 1;
+            }
+            .label-chip {
+                display: inline-block;
+                color: #fff;
+                font-family: sans-serif;
+                font-size: 12px;
+                border-radius: 10px;
+                padding: 2px 8px;
+            }
+            .label-picker-empty {
+                font-family: sans-serif;
+                font-size: 12px;
+                color: #808080;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+#[function_component(LabelPicker)]
+pub fn label_picker(props: &LabelPickerProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+
+    html! {
+        <StyledLabelPicker>
+            {if context.labels.is_empty() {
+                html!(<span class="label-picker-empty">{"No labels defined for this team"}</span>)
+            } else {
+                context.labels.iter().map(|label| {
+                    let selected = props.selected.contains(&label.id);
+                    let toggle = {
+                        let id = label.id.clone();
+                        let current = props.selected.clone();
+                        let onchange = props.onchange.clone();
+                        Callback::from(move |_| {
+                            let mut updated = current.clone();
+                            if selected {
+                                updated.retain(|l| l != &id);
+                            } else {
+                                updated.push(id.clone());
+                            }
+                            onchange.emit(updated);
+                        })
+                    };
+                    html! {
+                        <span
+                            class={classes!("label-option", selected.then_some("selected"))}
+                            onclick={toggle}
+                        >{label_chip(label)}</span>
+                    }
+                }).collect::<Html>()
+            }}
+        </StyledLabelPicker>
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{common::ComponentProps, markdown::render_markdown};
+use stylist::yew::styled_component;
+use web_sys::{Event, HtmlTextAreaElement};
+use yew::{classes, function_component, html, use_state, Callback, Html, Properties, TargetCast};
+
+#[derive(Properties, PartialEq)]
+pub struct MarkdownProps {
+    pub source: String,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct MarkdownEditorProps {
+    pub value: String,
+    pub onchange: Callback<String>,
+}
+
+#[styled_component(StyledMarkdown)]
+fn styled_markdown(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            min-width: 0;
+            flex-grow: 1;
+            font-family: sans-serif;
+            font-size: 14px;
+            line-height: 1.4;
+            overflow-wrap: anywhere;
+            p, ul, ol, pre, blockquote, table {
+                margin: 0 0 8px;
+            }
+            ul, ol {
+                padding-left: 20px;
+            }
+            code {
+                font-family: monospace;
+                background-color: #f0f0f0;
+                border-radius: 3px;
+                padding: 1px 4px;
+            }
+            pre {
+                background-color: #f0f0f0;
+                border-radius: 3px;
+                padding: 8px;
+                overflow-x: auto;
+            }
+            pre code {
+                padding: 0;
+            }
+            blockquote {
+                border-left: 3px solid #d3d3d3;
+                padding-left: 8px;
+                color: #808080;
+            }
+            a {
+                color: #1976d2;
+            }
+            img {
+                max-width: 100%;
+            }
+            table {
+                border-collapse: collapse;
+            }
+            th, td {
+                border: 1px solid #d3d3d3;
+                padding: 2px 6px;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+#[styled_component(StyledMarkdownEditor)]
+fn styled_markdown_editor(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            display: flex;
+            flex-direction: column;
+            flex-grow: 1;
+            .markdown-tabs > span {
+                display: inline-block;
+                padding: 4px 10px;
+                font-size: 12px;
+                color: #808080;
+                cursor: pointer;
+                border-bottom: 2px solid transparent;
+            }
+            .markdown-tabs > span.selected {
+                color: #4c4e52;
+                border-bottom-color: #74be43;
+            }
+            textarea {
+                margin-top: 4px;
+                width: 100%;
+                min-height: 120px;
+                box-sizing: border-box;
+                font-family: monospace;
+                resize: vertical;
+            }
+            .markdown-preview {
+                display: flex;
+                margin-top: 4px;
+                min-height: 120px;
+                padding: 4px;
+                border: 1px solid #d3d3d3;
+                border-radius: 3px;
+            }
+            .markdown-hint {
+                font-size: 11px;
+                color: #808080;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+/// Renders sanitized Markdown.
+#[function_component(Markdown)]
+pub fn markdown(props: &MarkdownProps) -> Html {
+    // Yew 0.19 has no way to render an HTML string, so it is parsed into a
+    // detached element that is handed over as a node reference.
+    let element = gloo_utils::document().create_element("div").unwrap();
+    element.set_inner_html(&render_markdown(&props.source));
+
+    html! {
+        <StyledMarkdown>{Html::VRef(element.into())}</StyledMarkdown>
+    }
+}
+
+/// A Markdown textarea with write and preview tabs.
+#[function_component(MarkdownEditor)]
+pub fn markdown_editor(props: &MarkdownEditorProps) -> Html {
+    let preview = use_state(|| false);
+
+    let show = |value: bool| {
+        let preview = preview.clone();
+        Callback::from(move |_| {
+            preview.set(value);
+        })
+    };
+
+    html! {
+        <StyledMarkdownEditor>
+            <div class="markdown-tabs">
+                <span class={classes!((!*preview).then_some("selected"))} onclick={show(false)}>
+                    {"Write"}
+                </span>
+                <span class={classes!(preview.then_some("selected"))} onclick={show(true)}>
+                    {"Preview"}
+                </span>
+            </div>
+            {if *preview {
+                html! {
+                    <div class="markdown-preview">
+                        {if props.value.trim().is_empty() {
+                            html!(<span class="markdown-hint">{"Nothing to preview"}</span>)
+                        } else {
+                            html!(<Markdown source={props.value.clone()} />)
+                        }}
+                    </div>
+                }
+            } else {
+                html! {
+                    <>
+                        <textarea
+                            value={props.value.clone()}
+                            onchange={{
+                                let onchange = props.onchange.clone();
+                                Callback::from(move |e: Event| {
+                                    onchange.emit(
+                                        e.target_dyn_into::<HtmlTextAreaElement>().unwrap().value()
+                                    );
+                                })
+                            }}
+                        />
+                        <span class="markdown-hint">{"Markdown is supported"}</span>
+                    </>
+                }
+            }}
+        </StyledMarkdownEditor>
+    }
+}
@@ -1,7 +1,29 @@
+pub mod activity_log;
+pub mod archive_modal;
+pub mod attachment_panel;
 pub mod board;
 pub mod card;
 pub mod card_create_modal;
 pub mod card_edit_modal;
+pub mod card_menu;
+pub mod checklist;
+pub mod comment_panel;
+pub mod custom_fields;
+pub mod duplicate_modal;
+pub mod filter_bar;
 pub mod header;
 pub mod home;
+pub mod label_picker;
+pub mod markdown_editor;
 pub mod modal;
+pub mod move_team_modal;
+pub mod shortcut_help_modal;
+pub mod subtasks;
+pub mod team_settings_modal;
+pub mod templates;
+pub mod ticket_links;
+pub mod ticket_search;
+pub mod trash_modal;
+pub mod undo_toast;
+pub mod user_picker;
+pub mod watchers;
@@ -1,7 +1,12 @@
-use crate::common::ComponentProps;
+use crate::common::{is_typing, ComponentProps};
+use gloo_events::EventListener;
 use stylist::yew::styled_component;
-use web_sys::MouseEvent;
-use yew::{create_portal, function_component, html, Callback, Children, Properties};
+use wasm_bindgen::JsCast;
+use web_sys::{Element, KeyboardEvent, MouseEvent};
+use yew::{
+    classes, create_portal, function_component, html, use_effect_with_deps, use_node_ref, Callback,
+    Children, NodeRef, Properties,
+};
 
 #[derive(Properties, PartialEq)]
 pub struct ModalProps {
@@ -10,8 +15,29 @@ pub struct ModalProps {
     pub close: Callback<()>,
 }
 
+#[derive(Properties, PartialEq)]
+struct StyledModalProps {
+    #[prop_or_default]
+    children: Children,
+    close: Callback<()>,
+    node_ref: NodeRef,
+}
+
+/// Whether no other modal was opened on top of `modal`. Modals share one
+/// portal host, so the ones opened later are its following siblings.
+fn is_topmost(modal: &Element) -> bool {
+    let mut sibling = modal.next_element_sibling();
+    while let Some(element) = sibling {
+        if element.matches(".modal").unwrap_or(false) {
+            return false;
+        }
+        sibling = element.next_element_sibling();
+    }
+    true
+}
+
 #[styled_component(StyledModal)]
-fn styled_modal(props: &ModalProps) -> Html {
+fn styled_modal(props: &StyledModalProps) -> Html {
     let close_modal = {
         let close = props.close.clone();
         Callback::from(move |_| {
@@ -20,7 +46,7 @@ fn styled_modal(props: &ModalProps) -> Html {
     };
 
     html! {
-        <div class={css!("
+        <div class={classes!(css!("
             position: absolute;
             top: 0;
             display: flex;
@@ -29,7 +55,7 @@ fn styled_modal(props: &ModalProps) -> Html {
             width: 100%;
             height: 100%;
             background-color: rgba(0, 0, 0, 0.5);
-        ")} onclick={close_modal}>{props.children.clone()}</div>
+        "), "modal")} ref={props.node_ref.clone()} onclick={close_modal}>{props.children.clone()}</div>
     }
 }
 
@@ -38,10 +64,11 @@ fn styled_modal_inner(props: &ComponentProps) -> Html {
     html! {
         <div class={css!("
             display: flex;
-            height: 350px;
+            min-height: 350px;
             width: 650px;
             max-width: 100%;
-            max-height: 100%;
+            max-height: 90%;
+            overflow-y: auto;
             border-radius: 5px;
             background-color: #ffffff;
         ")} onclick={|e: MouseEvent| e.stop_propagation()}>{props.children.clone()}</div>
@@ -50,13 +77,41 @@ fn styled_modal_inner(props: &ComponentProps) -> Html {
 
 #[function_component(Modal)]
 pub fn modal(props: &ModalProps) -> Html {
+    let node_ref = use_node_ref();
+    {
+        let close = props.close.clone();
+        let node_ref = node_ref.clone();
+        use_effect_with_deps(
+            move |close| {
+                let close = close.clone();
+                let listener = EventListener::new(&gloo_utils::document(), "keydown", move |e| {
+                    let e = match e.dyn_ref::<KeyboardEvent>() {
+                        Some(e) => e,
+                        None => return,
+                    };
+                    if e.key() != "Escape" || e.default_prevented() || is_typing(e) {
+                        return;
+                    }
+                    // Only the modal on top closes; the ones below stay open.
+                    if node_ref
+                        .cast::<Element>()
+                        .is_some_and(|modal| is_topmost(&modal))
+                    {
+                        close.emit(());
+                    }
+                });
+                move || drop(listener)
+            },
+            close,
+        );
+    }
     let host = gloo_utils::document()
         .get_element_by_id("root")
         .expect("root element not found");
 
     create_portal(
         html! {
-            <StyledModal close={props.close.clone()}>
+            <StyledModal close={props.close.clone()} {node_ref}>
                 <StyledModalInner>{props.children.clone()}</StyledModalInner>
             </StyledModal>
         },
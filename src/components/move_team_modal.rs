@@ -0,0 +1,385 @@
+use super::{
+    card_edit_modal::STATUSES,
+    checklist::{checklist_progress, completion_blocker},
+    modal::Modal,
+};
+use crate::{
+    common::ComponentProps, fetch_fields, fetch_labels, AppContext, CustomField, Label, Ticket,
+};
+use gloo_net::http::Request;
+use serde_json::{json, Value};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use stylist::yew::styled_component;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{Event, HtmlSelectElement};
+use yew::{
+    function_component, html, use_context, use_effect_with_deps, use_state, Callback, Html,
+    Properties, TargetCast,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct MoveTeamModalProps {
+    pub ticket: Rc<Ticket>,
+    pub close: Callback<()>,
+}
+
+/// Labels and custom fields of the team a ticket is moving to, with the
+/// chosen mapping onto them. Source label or field id to target id; missing
+/// entries are dropped.
+#[derive(Clone, PartialEq)]
+struct TargetTeam {
+    id: String,
+    labels: Vec<Label>,
+    fields: Vec<CustomField>,
+    label_map: HashMap<String, String>,
+    field_map: HashMap<String, String>,
+}
+
+/// Moves a ticket to another team, keeping its comments and history.
+/// `labels` and `custom_fields` are already remapped to the target team.
+async fn move_ticket(
+    id: String,
+    team_id: String,
+    status: String,
+    labels: Vec<String>,
+    custom_fields: HashMap<String, Value>,
+) {
+    let _response = Request::post(&format!("/api/tickets/{}/move/", id))
+        .json(&json!({
+            "team_id": team_id,
+            "status": status,
+            "labels": labels,
+            "custom_fields": custom_fields,
+        }))
+        .unwrap()
+        .send()
+        .await;
+}
+
+/// Pairs each source label with the target label of the same name.
+fn match_labels(ticket: &Ticket, target: &[Label]) -> HashMap<String, String> {
+    ticket
+        .labels
+        .iter()
+        .filter_map(|label| {
+            target
+                .iter()
+                .find(|t| t.name.eq_ignore_ascii_case(&label.name))
+                .map(|t| (label.id.clone(), t.id.clone()))
+        })
+        .collect()
+}
+
+/// Pairs each source field with the target field of the same name and kind.
+fn match_fields(source: &[CustomField], target: &[CustomField]) -> HashMap<String, String> {
+    source
+        .iter()
+        .filter_map(|field| {
+            target
+                .iter()
+                .find(|t| t.kind == field.kind && t.name.eq_ignore_ascii_case(&field.name))
+                .map(|t| (field.id.clone(), t.id.clone()))
+        })
+        .collect()
+}
+
+fn selected_value(e: Event) -> String {
+    e.target_dyn_into::<HtmlSelectElement>().unwrap().value()
+}
+
+#[styled_component(StyledMoveTeamModal)]
+fn styled_move_team_modal(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            display: flex;
+            flex-direction: column;
+            width: 100%;
+            padding: 16px;
+            font-family: sans-serif;
+            font-size: 14px;
+            color: #4c4e52;
+            .move-team-title {
+                font-size: 22px;
+            }
+            .move-team-section-title {
+                margin-top: 20px;
+                font-size: 16px;
+                color: #808080;
+            }
+            .move-team-hint {
+                font-size: 12px;
+                color: #808080;
+            }
+            .move-team-group {
+                display: flex;
+                align-items: center;
+                margin-top: 8px;
+            }
+            .move-team-group > span {
+                width: 35%;
+            }
+            .move-team-group > select {
+                flex-grow: 1;
+            }
+            .move-team-error {
+                margin-top: 20px;
+                font-size: 12px;
+                color: #f44336;
+            }
+            .move-team-action-group {
+                display: flex;
+                justify-content: end;
+                margin-top: 20px;
+            }
+            .move-team-action-group > button {
+                margin-left: 5px;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+/// Moves a ticket to another team. Labels and custom fields only exist per
+/// team, so each one is remapped to a target team label or field, or dropped.
+#[function_component(MoveTeamModal)]
+pub fn move_team_modal(props: &MoveTeamModalProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let current_team_id = context.current_team_id();
+    let team_id = use_state(|| String::from(""));
+    let status = use_state(|| props.ticket.status.clone());
+    let target = use_state(|| None::<TargetTeam>);
+    let source_fields = context
+        .fields
+        .iter()
+        .filter(|field| props.ticket.custom_fields.contains_key(&field.id))
+        .cloned()
+        .collect::<Vec<CustomField>>();
+
+    {
+        let target = target.clone();
+        let ticket = props.ticket.clone();
+        let source_fields = source_fields.clone();
+        use_effect_with_deps(
+            move |team_id| {
+                let team_id = (**team_id).clone();
+                // Set once another team is picked, so a slow response for
+                // the previous one is dropped.
+                let stale = Rc::new(Cell::new(false));
+                if !team_id.is_empty() {
+                    let stale = stale.clone();
+                    spawn_local(async move {
+                        let labels = fetch_labels(team_id.clone()).await;
+                        let fields = fetch_fields(team_id.clone()).await;
+                        if stale.get() {
+                            return;
+                        }
+                        target.set(Some(TargetTeam {
+                            id: team_id,
+                            label_map: match_labels(&ticket, &labels),
+                            field_map: match_fields(&source_fields, &fields),
+                            labels,
+                            fields,
+                        }));
+                    });
+                }
+                move || stale.set(true)
+            },
+            team_id.clone(),
+        );
+    }
+    // The loaded target, once it belongs to the selected team.
+    let loaded = target
+        .as_ref()
+        .filter(|target| target.id == *team_id)
+        .cloned();
+    // Moving counts as entering the status on the target team.
+    let blocker = context
+        .teams
+        .iter()
+        .find(|team| team.id == *team_id)
+        .and_then(|team| {
+            completion_blocker(Some(team), &status, checklist_progress(&props.ticket))
+        });
+
+    let save = {
+        let id = props.ticket.id.clone();
+        let ticket = props.ticket.clone();
+        let target = loaded.clone();
+        let status = status.clone();
+        let update_tickets = context.update_tickets.clone();
+        let update_open_ticket = context.update_open_ticket.clone();
+        let close = props.close.clone();
+        Callback::from(move |_| {
+            let target = match &target {
+                Some(target) => target,
+                None => return,
+            };
+            let labels = ticket
+                .labels
+                .iter()
+                .filter_map(|label| target.label_map.get(&label.id).cloned())
+                .collect::<Vec<String>>();
+            let custom_fields = ticket
+                .custom_fields
+                .iter()
+                .filter_map(|(id, value)| {
+                    target
+                        .field_map
+                        .get(id)
+                        .map(|target_id| (target_id.clone(), value.clone()))
+                })
+                .collect::<HashMap<String, Value>>();
+            let id = id.clone();
+            let team_id = target.id.clone();
+            let status = (*status).clone();
+            let update_tickets = update_tickets.clone();
+            let update_open_ticket = update_open_ticket.clone();
+            let close = close.clone();
+            spawn_local(async move {
+                move_ticket(id, team_id, status, labels, custom_fields).await;
+                // The ticket is no longer on this team's board.
+                update_open_ticket.emit(None);
+                update_tickets.emit(());
+                close.emit(());
+            });
+        })
+    };
+
+    html! {
+        <Modal close={props.close.clone()}>
+            <StyledMoveTeamModal>
+                <div class="move-team-title">{"Move to team"}</div>
+                <div class="move-team-hint">{"Comments, attachments and history move with the ticket."}</div>
+                <div class="move-team-group">
+                    <span>{"Team:"}</span>
+                    <select onchange={{
+                        let team_id = team_id.clone();
+                        Callback::from(move |e: Event| {
+                            team_id.set(selected_value(e));
+                        })
+                    }}>
+                        <option value="" selected={team_id.is_empty()}>{"Choose a team"}</option>
+                        {context.teams.iter().filter(|team| team.id != current_team_id).map(|team| {
+                            html! {
+                                <option
+                                    value={team.id.clone()}
+                                    selected={team.id == *team_id}
+                                >{team.name.clone()}</option>
+                            }
+                        }).collect::<Html>()}
+                    </select>
+                </div>
+                <div class="move-team-group">
+                    <span>{"Status:"}</span>
+                    <select onchange={{
+                        let status = status.clone();
+                        Callback::from(move |e: Event| {
+                            status.set(selected_value(e));
+                        })
+                    }}>
+                        {STATUSES.iter().map(|s| {
+                            html! {
+                                <option value={s.value} selected={s.value == *status}>
+                                    {s.label}
+                                </option>
+                            }
+                        }).collect::<Html>()}
+                    </select>
+                </div>
+                {match &loaded {
+                    Some(loaded) if !props.ticket.labels.is_empty() => html! {
+                        <>
+                            <div class="move-team-section-title">{"Labels"}</div>
+                            {props.ticket.labels.iter().map(|label| {
+                                let onchange = {
+                                    let id = label.id.clone();
+                                    let target = target.clone();
+                                    let loaded = loaded.clone();
+                                    Callback::from(move |e: Event| {
+                                        let mut updated = loaded.clone();
+                                        match selected_value(e) {
+                                            value if value.is_empty() => updated.label_map.remove(&id),
+                                            value => updated.label_map.insert(id.clone(), value),
+                                        };
+                                        target.set(Some(updated));
+                                    })
+                                };
+                                let selected = loaded.label_map.get(&label.id);
+                                html! {
+                                    <div class="move-team-group" key={label.id.clone()}>
+                                        <span>{label.name.clone()}</span>
+                                        <select {onchange}>
+                                            <option value="" selected={selected.is_none()}>{"Drop"}</option>
+                                            {loaded.labels.iter().map(|t| {
+                                                html! {
+                                                    <option
+                                                        value={t.id.clone()}
+                                                        selected={selected == Some(&t.id)}
+                                                    >{t.name.clone()}</option>
+                                                }
+                                            }).collect::<Html>()}
+                                        </select>
+                                    </div>
+                                }
+                            }).collect::<Html>()}
+                        </>
+                    },
+                    _ => html!(),
+                }}
+                {match &loaded {
+                    Some(loaded) if !source_fields.is_empty() => html! {
+                        <>
+                            <div class="move-team-section-title">{"Custom fields"}</div>
+                            {source_fields.iter().map(|field| {
+                                let onchange = {
+                                    let id = field.id.clone();
+                                    let target = target.clone();
+                                    let loaded = loaded.clone();
+                                    Callback::from(move |e: Event| {
+                                        let mut updated = loaded.clone();
+                                        match selected_value(e) {
+                                            value if value.is_empty() => updated.field_map.remove(&id),
+                                            value => updated.field_map.insert(id.clone(), value),
+                                        };
+                                        target.set(Some(updated));
+                                    })
+                                };
+                                let selected = loaded.field_map.get(&field.id);
+                                html! {
+                                    <div class="move-team-group" key={field.id.clone()}>
+                                        <span>{field.name.clone()}</span>
+                                        <select {onchange}>
+                                            <option value="" selected={selected.is_none()}>{"Drop"}</option>
+                                            // Values only carry over between fields of the same kind.
+                                            {loaded.fields.iter().filter(|t| t.kind == field.kind).map(|t| {
+                                                html! {
+                                                    <option
+                                                        value={t.id.clone()}
+                                                        selected={selected == Some(&t.id)}
+                                                    >{t.name.clone()}</option>
+                                                }
+                                            }).collect::<Html>()}
+                                        </select>
+                                    </div>
+                                }
+                            }).collect::<Html>()}
+                        </>
+                    },
+                    _ => html!(),
+                }}
+                {match blocker.clone() {
+                    Some(reason) => html!(<div class="move-team-error">{reason}</div>),
+                    None => html!(),
+                }}
+                <div class="move-team-action-group">
+                    <button onclick={props.close.reform(|_| ())}>{"Cancel"}</button>
+                    <button
+                        disabled={loaded.is_none() || blocker.is_some()}
+                        onclick={save}
+                    >{"Move"}</button>
+                </div>
+            </StyledMoveTeamModal>
+        </Modal>
+    }
+}
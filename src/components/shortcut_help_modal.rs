@@ -0,0 +1,112 @@
+use super::modal::Modal;
+use crate::common::ComponentProps;
+use stylist::yew::styled_component;
+use yew::{function_component, html, Callback, Html, Properties};
+
+#[derive(Properties, PartialEq)]
+pub struct ShortcutHelpModalProps {
+    pub close: Callback<()>,
+}
+
+pub struct Shortcut {
+    pub keys: &'static str,
+    pub description: &'static str,
+}
+
+pub const SHORTCUTS: &[Shortcut] = &[
+    Shortcut {
+        keys: "j / ↓",
+        description: "Focus next card",
+    },
+    Shortcut {
+        keys: "k / ↑",
+        description: "Focus previous card",
+    },
+    Shortcut {
+        keys: "h / ←",
+        description: "Focus previous column",
+    },
+    Shortcut {
+        keys: "l / →",
+        description: "Focus next column",
+    },
+    Shortcut {
+        keys: "Enter",
+        description: "Open focused card",
+    },
+    Shortcut {
+        keys: "e",
+        description: "Edit focused card",
+    },
+    Shortcut {
+        keys: "a",
+        description: "Set the owner of the focused card",
+    },
+    Shortcut {
+        keys: "]",
+        description: "Move focused card to next status",
+    },
+    Shortcut {
+        keys: "[",
+        description: "Move focused card to previous status",
+    },
+    Shortcut {
+        keys: "c",
+        description: "Create card in focused column",
+    },
+    Shortcut {
+        keys: "Escape",
+        description: "Close modal",
+    },
+    Shortcut {
+        keys: "?",
+        description: "Show this help",
+    },
+];
+
+#[styled_component(StyledShortcutHelpModal)]
+fn styled_shortcut_help_modal(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            display: flex;
+            flex-direction: column;
+            width: 100%;
+            padding: 16px;
+            overflow: auto;
+            font-family: sans-serif;
+            font-size: 14px;
+            color: #4c4e52;
+            .shortcut-help-title {
+                font-size: 22px;
+                margin-bottom: 12px;
+            }
+            .shortcut-help-row {
+                display: flex;
+                padding: 4px 0;
+            }
+            .shortcut-help-row > kbd {
+                width: 30%;
+                font-family: monospace;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+#[function_component(ShortcutHelpModal)]
+pub fn shortcut_help_modal(props: &ShortcutHelpModalProps) -> Html {
+    html! {
+        <Modal close={props.close.clone()}>
+            <StyledShortcutHelpModal>
+                <div class="shortcut-help-title">{"Keyboard Shortcuts"}</div>
+                {SHORTCUTS.iter().map(|shortcut| {
+                    html! {
+                        <div class="shortcut-help-row">
+                            <kbd>{shortcut.keys}</kbd>
+                            <span>{shortcut.description}</span>
+                        </div>
+                    }
+                }).collect::<Html>()}
+            </StyledShortcutHelpModal>
+        </Modal>
+    }
+}
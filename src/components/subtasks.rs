@@ -0,0 +1,242 @@
+use super::{
+    card_create_modal::{create_ticket, NewTicket},
+    card_edit_modal::{status_label, update_ticket, STATUSES},
+    ticket_links::is_closed,
+    ticket_search::TicketSearch,
+};
+use crate::{common::ComponentProps, AppContext, LinkedTicket, OpenTicket, Ticket, TicketFilters};
+use serde_json::Value;
+use std::rc::Rc;
+use stylist::yew::styled_component;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{Event, HtmlInputElement};
+use yew::{
+    classes, function_component, html, use_context, use_state, Callback, Html, Properties,
+    TargetCast,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct SubtasksProps {
+    pub ticket: Rc<Ticket>,
+}
+
+/// Accepted and total sub-tasks.
+pub fn subtask_progress(ticket: &Ticket) -> (usize, usize) {
+    let done = ticket
+        .subtasks
+        .iter()
+        .filter(|subtask| subtask.status == "Complete")
+        .count();
+    (done, ticket.subtasks.len())
+}
+
+/// Makes `ticket_id` a sub-task of `parent_id`, or a top-level ticket when
+/// `parent_id` is `None`.
+async fn set_parent(ticket_id: String, parent_id: Option<String>) {
+    let parent_id = parent_id.map(Value::from).unwrap_or(Value::Null);
+    update_ticket(ticket_id, String::from("parent_id"), parent_id).await;
+}
+
+#[styled_component(StyledSubtasks)]
+fn styled_subtasks(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            margin-top: 20px;
+            .subtasks-header {
+                display: flex;
+                align-items: center;
+            }
+            .subtasks-title {
+                flex-grow: 1;
+                font-size: 16px;
+                color: #808080;
+            }
+            .subtasks-header > button {
+                margin-left: 5px;
+            }
+            .subtask {
+                display: flex;
+                align-items: center;
+                margin-top: 4px;
+            }
+            .subtask > a {
+                flex-grow: 1;
+                color: #1976d2;
+                cursor: pointer;
+            }
+            .subtask.closed > a {
+                text-decoration: line-through;
+            }
+            .subtask > span {
+                margin: 0 8px;
+                font-size: 12px;
+                color: #808080;
+            }
+            .subtasks-add {
+                display: flex;
+                margin-top: 8px;
+            }
+            .subtasks-add > input {
+                flex-grow: 1;
+            }
+            .subtasks-add > button {
+                margin-left: 5px;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+/// The ticket's parent epic and its sub-tasks.
+#[function_component(Subtasks)]
+pub fn subtasks(props: &SubtasksProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let new_title = use_state(|| String::from(""));
+    let (done, total) = subtask_progress(&props.ticket);
+
+    let open = |ticket: &LinkedTicket| {
+        let id = ticket.id.clone();
+        let update_open_ticket = context.update_open_ticket.clone();
+        Callback::from(move |_| {
+            update_open_ticket.emit(Some(OpenTicket {
+                id: id.clone(),
+                field: None,
+            }));
+        })
+    };
+    let detach = |ticket_id: String| {
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |_| {
+            let ticket_id = ticket_id.clone();
+            let update_tickets = update_tickets.clone();
+            spawn_local(async move {
+                set_parent(ticket_id, None).await;
+                update_tickets.emit(());
+            });
+        })
+    };
+    let add_subtask = {
+        let new_title = new_title.clone();
+        let parent_id = props.ticket.id.clone();
+        let team_id = context.current_team_id();
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |_| {
+            let title = (*new_title).trim().to_string();
+            if title.is_empty() {
+                return;
+            }
+            let new_ticket = NewTicket {
+                title,
+                status: String::from(STATUSES[0].value),
+                parent_id: parent_id.clone(),
+                team_id: team_id.clone(),
+                ..Default::default()
+            };
+            let update_tickets = update_tickets.clone();
+            new_title.set(String::from(""));
+            spawn_local(async move {
+                create_ticket(&new_ticket).await;
+                update_tickets.emit(());
+            });
+        })
+    };
+    let add_existing = {
+        let parent_id = props.ticket.id.clone();
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |ticket: Ticket| {
+            let parent_id = parent_id.clone();
+            let update_tickets = update_tickets.clone();
+            spawn_local(async move {
+                set_parent(ticket.id, Some(parent_id)).await;
+                update_tickets.emit(());
+            });
+        })
+    };
+    let show_on_board = {
+        let epic = LinkedTicket::from(props.ticket.as_ref());
+        let filters = context.filters.clone();
+        let update_filters = context.update_filters.clone();
+        let update_open_ticket = context.update_open_ticket.clone();
+        Callback::from(move |_| {
+            update_filters.emit(TicketFilters {
+                epic: Some(epic.clone()),
+                ..filters.clone()
+            });
+            update_open_ticket.emit(None);
+        })
+    };
+    // Picked tickets are nested under this one, so it and its parent would
+    // form a cycle, and existing sub-tasks are already here. Only the direct
+    // parent is known here; the backend rejects deeper ancestors.
+    let exclude = props
+        .ticket
+        .subtasks
+        .iter()
+        .chain(props.ticket.parent.iter())
+        .map(|ticket| ticket.id.clone())
+        .chain(std::iter::once(props.ticket.id.clone()))
+        .collect::<Vec<String>>();
+
+    html! {
+        <StyledSubtasks>
+            <div class="subtasks-header">
+                <span class="subtasks-title">
+                    {if total > 0 {
+                        format!("Sub-tasks ({}/{} accepted)", done, total)
+                    } else {
+                        String::from("Sub-tasks")
+                    }}
+                </span>
+                {if total > 0 {
+                    html!(<button onclick={show_on_board}>{"Show on board"}</button>)
+                } else {
+                    html!()
+                }}
+            </div>
+            {match &props.ticket.parent {
+                Some(parent) => html! {
+                    <div class="subtask">
+                        <span>{"Parent:"}</span>
+                        <a onclick={open(parent)}>{parent.name()}</a>
+                        <button onclick={detach(props.ticket.id.clone())}>
+                            {"Remove parent"}
+                        </button>
+                    </div>
+                },
+                None => html!(),
+            }}
+            {props.ticket.subtasks.iter().map(|subtask| {
+                html! {
+                    <div
+                        class={classes!("subtask", is_closed(&subtask.status).then_some("closed"))}
+                        key={subtask.id.clone()}
+                    >
+                        <a onclick={open(subtask)}>{subtask.name()}</a>
+                        <span>{status_label(&subtask.status)}</span>
+                        <button onclick={detach(subtask.id.clone())}>{"Remove"}</button>
+                    </div>
+                }
+            }).collect::<Html>()}
+            <div class="subtasks-add">
+                <input
+                    type="text"
+                    placeholder="New sub-task"
+                    value={(*new_title).clone()}
+                    onchange={{
+                        let new_title = new_title.clone();
+                        Callback::from(move |e: Event| {
+                            new_title.set(e.target_dyn_into::<HtmlInputElement>().unwrap().value());
+                        })
+                    }}
+                />
+                <button onclick={add_subtask}>{"Add"}</button>
+            </div>
+            <div class="subtasks-add">
+                <TicketSearch
+                    onselect={add_existing}
+                    exclude={exclude}
+                    placeholder={String::from("Add an existing ticket...")}
+                />
+            </div>
+        </StyledSubtasks>
+    }
+}
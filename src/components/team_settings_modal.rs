@@ -0,0 +1,388 @@
+use super::{
+    custom_fields::{CustomFieldSettings, FormLayoutSettings},
+    label_picker::label_chip,
+    modal::Modal,
+    templates::TemplateSettings,
+};
+use crate::{common::ComponentProps, AppContext, Label};
+use gloo_net::http::Request;
+use serde_json::{json, Value};
+use stylist::yew::styled_component;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{Event, HtmlInputElement};
+use yew::{
+    function_component, html, use_context, use_state, Callback, Html, Properties, TargetCast,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct TeamSettingsModalProps {
+    pub close: Callback<()>,
+}
+
+#[derive(Properties, PartialEq)]
+struct LabelRowProps {
+    label: Label,
+}
+
+const DEFAULT_LABEL_COLOR: &str = "#1976d2";
+
+/// Longest allowed ticket key prefix.
+const MAX_KEY_LENGTH: usize = 10;
+
+/// Upper-cases a ticket key prefix and drops anything but letters and digits.
+fn normalize_key(key: &str) -> String {
+    key.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .take(MAX_KEY_LENGTH)
+        .collect::<String>()
+        .to_ascii_uppercase()
+}
+
+async fn update_team(id: String, key: String, value: Value) {
+    let _response = Request::put(&format!("/api/teams/{}", id))
+        .json(&json!({
+            key: value,
+        }))
+        .unwrap()
+        .send()
+        .await;
+}
+
+async fn create_label(team_id: String, name: String, color: String) {
+    let _response = Request::post(&format!("/api/teams/{}/labels/", team_id))
+        .json(&json!({
+            "name": name,
+            "color": color,
+        }))
+        .unwrap()
+        .send()
+        .await;
+}
+
+async fn update_label(id: String, name: String, color: String) {
+    let _response = Request::put(&format!("/api/labels/{}", id))
+        .json(&json!({
+            "name": name,
+            "color": color,
+        }))
+        .unwrap()
+        .send()
+        .await;
+}
+
+async fn delete_label(id: String) {
+    let _response = Request::delete(&format!("/api/labels/{}", id)).send().await;
+}
+
+#[styled_component(StyledTeamSettingsModal)]
+fn styled_team_settings_modal(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            display: flex;
+            flex-direction: column;
+            width: 100%;
+            padding: 16px;
+            font-family: sans-serif;
+            font-size: 14px;
+            color: #4c4e52;
+            .team-settings-title {
+                font-size: 22px;
+            }
+            .team-settings-section {
+                margin-top: 20px;
+            }
+            .team-settings-section-title {
+                font-size: 16px;
+                color: #808080;
+                margin-bottom: 8px;
+            }
+            .team-settings-row {
+                display: flex;
+                align-items: center;
+                margin-top: 6px;
+            }
+            .team-settings-row > label {
+                display: flex;
+                align-items: center;
+            }
+            .team-settings-row > input[type=text] {
+                flex-grow: 1;
+                margin: 0 5px;
+            }
+            .team-settings-row > input[type=number] {
+                width: 60px;
+                margin: 0 5px;
+            }
+            .team-settings-row > select {
+                margin-right: 5px;
+            }
+            .team-settings-row > button {
+                margin-left: 5px;
+            }
+            .team-settings-template {
+                padding: 8px;
+                margin-top: 6px;
+                border: 1px solid #d3d3d3;
+                border-radius: 4px;
+            }
+            .team-settings-template .team-settings-row > span {
+                width: 20%;
+            }
+            .team-settings-template textarea {
+                flex-grow: 1;
+                min-height: 60px;
+                margin: 0 5px;
+                font-family: inherit;
+            }
+            .team-settings-name {
+                flex-grow: 1;
+            }
+            .team-settings-name.hidden-field {
+                color: #808080;
+            }
+            .team-settings-actions {
+                justify-content: flex-end;
+            }
+            .label-chip {
+                display: inline-block;
+                min-width: 60px;
+                color: #fff;
+                font-size: 12px;
+                text-align: center;
+                border-radius: 10px;
+                padding: 2px 8px;
+            }
+            button {
+                cursor: pointer;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+#[function_component(LabelRow)]
+fn label_row(props: &LabelRowProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let name = use_state(|| props.label.name.clone());
+    let color = use_state(|| props.label.color.clone());
+
+    let save_label = {
+        let id = props.label.id.clone();
+        let name = name.clone();
+        let color = color.clone();
+        let update_labels = context.update_labels.clone();
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |_| {
+            let id = id.clone();
+            let name = (*name).clone();
+            let color = (*color).clone();
+            let update_labels = update_labels.clone();
+            let update_tickets = update_tickets.clone();
+            spawn_local(async move {
+                update_label(id, name, color).await;
+                update_labels.emit(());
+                update_tickets.emit(());
+            });
+        })
+    };
+    let delete_label = {
+        let id = props.label.id.clone();
+        let update_labels = context.update_labels.clone();
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |_| {
+            let id = id.clone();
+            let update_labels = update_labels.clone();
+            let update_tickets = update_tickets.clone();
+            spawn_local(async move {
+                delete_label(id).await;
+                update_labels.emit(());
+                update_tickets.emit(());
+            });
+        })
+    };
+
+    html! {
+        <div class="team-settings-row">
+            {label_chip(&Label {
+                id: props.label.id.clone(),
+                name: (*name).clone(),
+                color: (*color).clone(),
+            })}
+            <input
+                type="text"
+                value={(*name).clone()}
+                onchange={{
+                    let name = name.clone();
+                    Callback::from(move |e: Event| {
+                        name.set(e.target_dyn_into::<HtmlInputElement>().unwrap().value());
+                    })
+                }}
+            />
+            <input
+                type="color"
+                value={(*color).clone()}
+                onchange={{
+                    let color = color.clone();
+                    Callback::from(move |e: Event| {
+                        color.set(e.target_dyn_into::<HtmlInputElement>().unwrap().value());
+                    })
+                }}
+            />
+            <button onclick={save_label}>{"Save"}</button>
+            <button onclick={delete_label}>{"Delete"}</button>
+        </div>
+    }
+}
+
+#[function_component(TeamSettingsModal)]
+pub fn team_settings_modal(props: &TeamSettingsModalProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let new_label_name = use_state(|| String::from(""));
+    let new_label_color = use_state(|| String::from(DEFAULT_LABEL_COLOR));
+
+    let add_label = {
+        let team_id = context.current_team_id();
+        let new_label_name = new_label_name.clone();
+        let new_label_color = new_label_color.clone();
+        let update_labels = context.update_labels.clone();
+        Callback::from(move |_| {
+            let name = (*new_label_name).trim().to_string();
+            if name.is_empty() {
+                return;
+            }
+            let team_id = team_id.clone();
+            let color = (*new_label_color).clone();
+            let update_labels = update_labels.clone();
+            new_label_name.set(String::from(""));
+            spawn_local(async move {
+                create_label(team_id, name, color).await;
+                update_labels.emit(());
+            });
+        })
+    };
+
+    let update_setting = {
+        let team_id = context.current_team_id();
+        let update_teams = context.update_teams.clone();
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |(key, value): (String, Value)| {
+            let team_id = team_id.clone();
+            let update_teams = update_teams.clone();
+            let update_tickets = update_tickets.clone();
+            spawn_local(async move {
+                update_team(team_id, key, value).await;
+                update_teams.emit(());
+                // Ticket keys are built from the team's prefix.
+                update_tickets.emit(());
+            });
+        })
+    };
+    let require_checklist = context.team().map(|t| t.require_checklist).unwrap_or(false);
+    let team_key = context.team().map(|t| t.key.clone()).unwrap_or_default();
+    let auto_archive_days = context.team().and_then(|t| t.auto_archive_days);
+
+    html! {
+        <Modal close={props.close.clone()}>
+            <StyledTeamSettingsModal>
+                <div class="team-settings-title">
+                    {format!("{} Settings", context.current_team.clone().unwrap_or_default())}
+                </div>
+                <div class="team-settings-section">
+                    <div class="team-settings-section-title">{"Workflow"}</div>
+                    <div class="team-settings-row">
+                        <label>
+                            <input
+                                type="checkbox"
+                                checked={require_checklist}
+                                onchange={{
+                                    let update_setting = update_setting.clone();
+                                    Callback::from(move |e: Event| {
+                                        let checked =
+                                            e.target_dyn_into::<HtmlInputElement>().unwrap().checked();
+                                        update_setting.emit(("require_checklist".into(), checked.into()));
+                                    })
+                                }}
+                            />
+                            {"Require a fully checked checklist before accepting a ticket"}
+                        </label>
+                    </div>
+                    <div class="team-settings-row">
+                        <span>{"Ticket key prefix"}</span>
+                        <input
+                            type="text"
+                            placeholder="e.g. QE"
+                            maxlength={MAX_KEY_LENGTH.to_string()}
+                            value={team_key}
+                            onchange={{
+                                let update_setting = update_setting.clone();
+                                Callback::from(move |e: Event| {
+                                    let input = e.target_dyn_into::<HtmlInputElement>().unwrap();
+                                    let key = normalize_key(&input.value());
+                                    input.set_value(&key);
+                                    update_setting.emit(("key".into(), key.into()));
+                                })
+                            }}
+                        />
+                    </div>
+                    <div class="team-settings-row">
+                        <span>{"Archive accepted tickets after"}</span>
+                        <input
+                            type="number"
+                            min="1"
+                            placeholder="Never"
+                            value={auto_archive_days.map(|days| days.to_string()).unwrap_or_default()}
+                            onchange={{
+                                let update_setting = update_setting.clone();
+                                Callback::from(move |e: Event| {
+                                    let value = e.target_dyn_into::<HtmlInputElement>().unwrap().value();
+                                    // Empty or zero turns automatic archiving off.
+                                    let days = value.trim().parse::<u32>().ok().filter(|days| *days > 0);
+                                    update_setting.emit(("auto_archive_days".into(), days.into()));
+                                })
+                            }}
+                        />
+                        <span>{"days"}</span>
+                    </div>
+                </div>
+                <div class="team-settings-section">
+                    <div class="team-settings-section-title">{"Labels"}</div>
+                    {context.labels.iter().map(|label| {
+                        html! {<LabelRow key={label.id.clone()} label={label.clone()} />}
+                    }).collect::<Html>()}
+                    <div class="team-settings-row">
+                        <input
+                            type="text"
+                            placeholder="New label"
+                            value={(*new_label_name).clone()}
+                            onchange={{
+                                let new_label_name = new_label_name.clone();
+                                Callback::from(move |e: Event| {
+                                    new_label_name.set(
+                                        e.target_dyn_into::<HtmlInputElement>().unwrap().value()
+                                    );
+                                })
+                            }}
+                        />
+                        <input
+                            type="color"
+                            value={(*new_label_color).clone()}
+                            onchange={{
+                                let new_label_color = new_label_color.clone();
+                                Callback::from(move |e: Event| {
+                                    new_label_color.set(
+                                        e.target_dyn_into::<HtmlInputElement>().unwrap().value()
+                                    );
+                                })
+                            }}
+                        />
+                        <button onclick={add_label}>{"Add"}</button>
+                    </div>
+                </div>
+                <CustomFieldSettings />
+                <FormLayoutSettings onchange={update_setting.reform(|layout: Vec<String>| {
+                    ("layout".to_string(), layout.into())
+                })} />
+                <TemplateSettings />
+            </StyledTeamSettingsModal>
+        </Modal>
+    }
+}
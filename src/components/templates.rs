@@ -0,0 +1,273 @@
+use super::{card_edit_modal::STATUSES, label_picker::LabelPicker};
+use crate::{time::today_iso, AppContext, Template};
+use gloo_net::http::Request;
+use serde_json::{json, Value};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{Event, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+use yew::{
+    function_component, html, use_context, use_state, Callback, Html, Properties, TargetCast,
+};
+
+#[derive(Properties, PartialEq)]
+struct TemplateEditorProps {
+    /// `None` when the editor adds a new template.
+    #[prop_or_default]
+    template: Option<Template>,
+}
+
+async fn create_template(team_id: String, template: Value) {
+    let _response = Request::post(&format!("/api/teams/{}/templates/", team_id))
+        .json(&template)
+        .unwrap()
+        .send()
+        .await;
+}
+
+async fn update_template(id: String, template: Value) {
+    let _response = Request::put(&format!("/api/templates/{}", id))
+        .json(&template)
+        .unwrap()
+        .send()
+        .await;
+}
+
+async fn delete_template(id: String) {
+    let _response = Request::delete(&format!("/api/templates/{}", id))
+        .send()
+        .await;
+}
+
+/// Fills in a template's title pattern. `{date}` becomes today's date and
+/// `{team}` the team name.
+pub fn expand_title(pattern: &str, team: &str) -> String {
+    pattern
+        .replace("{date}", &today_iso())
+        .replace("{team}", team)
+}
+
+fn template_json(template: &Template) -> Value {
+    json!({
+        "name": template.name,
+        "title": template.title,
+        "description": template.description,
+        "labels": template.labels,
+        "owner": template.owner,
+        "status": template.status,
+        "checklist": template.checklist,
+    })
+}
+
+#[function_component(TemplateEditor)]
+fn template_editor(props: &TemplateEditorProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let template = use_state(|| props.template.clone().unwrap_or_default());
+    let expanded = use_state(|| false);
+    let is_new = props.template.is_none();
+
+    let save = {
+        let template = template.clone();
+        let expanded = expanded.clone();
+        let team_id = context.current_team_id();
+        let update_templates = context.update_templates.clone();
+        Callback::from(move |_| {
+            if template.name.trim().is_empty() {
+                return;
+            }
+            let id = template.id.clone();
+            let body = template_json(&template);
+            let team_id = team_id.clone();
+            let update_templates = update_templates.clone();
+            if id.is_empty() {
+                template.set(Template::default());
+            }
+            expanded.set(false);
+            spawn_local(async move {
+                if id.is_empty() {
+                    create_template(team_id, body).await;
+                } else {
+                    update_template(id, body).await;
+                }
+                update_templates.emit(());
+            });
+        })
+    };
+    let delete = {
+        let id = template.id.clone();
+        let update_templates = context.update_templates.clone();
+        Callback::from(move |_| {
+            let id = id.clone();
+            let update_templates = update_templates.clone();
+            spawn_local(async move {
+                delete_template(id).await;
+                update_templates.emit(());
+            });
+        })
+    };
+    let set = |update: fn(&mut Template, String)| {
+        let template = template.clone();
+        Callback::from(move |value: String| {
+            let mut updated = (*template).clone();
+            update(&mut updated, value);
+            template.set(updated);
+        })
+    };
+    let input_value = |callback: Callback<String>| {
+        Callback::from(move |e: Event| {
+            callback.emit(e.target_dyn_into::<HtmlInputElement>().unwrap().value());
+        })
+    };
+    let select_value = |callback: Callback<String>| {
+        Callback::from(move |e: Event| {
+            callback.emit(e.target_dyn_into::<HtmlSelectElement>().unwrap().value());
+        })
+    };
+
+    if !*expanded {
+        let expand = {
+            let expanded = expanded.clone();
+            Callback::from(move |_| {
+                expanded.set(true);
+            })
+        };
+        return html! {
+            <div class="team-settings-row">
+                {if is_new {
+                    html!(<button onclick={expand}>{"New template"}</button>)
+                } else {
+                    html! {
+                        <>
+                            <span class="team-settings-name">{template.name.clone()}</span>
+                            <button onclick={expand}>{"Edit"}</button>
+                            <button onclick={delete}>{"Delete"}</button>
+                        </>
+                    }
+                }}
+            </div>
+        };
+    }
+
+    html! {
+        <div class="team-settings-template">
+            <div class="team-settings-row">
+                <span>{"Name"}</span>
+                <input
+                    type="text"
+                    value={template.name.clone()}
+                    onchange={input_value(set(|t, v| t.name = v))}
+                />
+            </div>
+            <div class="team-settings-row">
+                <span>{"Title"}</span>
+                <input
+                    type="text"
+                    placeholder="e.g. Regression review {date}"
+                    value={template.title.clone()}
+                    onchange={input_value(set(|t, v| t.title = v))}
+                />
+            </div>
+            <div class="team-settings-row">
+                <span>{"Description"}</span>
+                <textarea
+                    value={template.description.clone()}
+                    onchange={{
+                        let set_description = set(|t, v| t.description = v);
+                        Callback::from(move |e: Event| {
+                            set_description.emit(
+                                e.target_dyn_into::<HtmlTextAreaElement>().unwrap().value()
+                            );
+                        })
+                    }}
+                />
+            </div>
+            <div class="team-settings-row">
+                <span>{"Checklist"}</span>
+                <textarea
+                    placeholder="One item per line"
+                    value={template.checklist.join("\n")}
+                    onchange={{
+                        let template = template.clone();
+                        Callback::from(move |e: Event| {
+                            let value = e.target_dyn_into::<HtmlTextAreaElement>().unwrap().value();
+                            template.set(Template {
+                                checklist: value
+                                    .lines()
+                                    .map(|line| line.trim().to_string())
+                                    .filter(|line| !line.is_empty())
+                                    .collect(),
+                                ..(*template).clone()
+                            });
+                        })
+                    }}
+                />
+            </div>
+            <div class="team-settings-row">
+                <span>{"Owner"}</span>
+                <select onchange={select_value(set(|t, v| t.owner = Some(v).filter(|v| !v.is_empty())))}>
+                    <option value="" selected={template.owner.is_none()}>{"No owner"}</option>
+                    {context.users.iter().map(|user| {
+                        html! {
+                            <option
+                                value={user.id.clone()}
+                                selected={template.owner.as_ref() == Some(&user.id)}
+                            >{user.name.clone()}</option>
+                        }
+                    }).collect::<Html>()}
+                </select>
+            </div>
+            <div class="team-settings-row">
+                <span>{"Status"}</span>
+                <select onchange={select_value(set(|t, v| t.status = Some(v).filter(|v| !v.is_empty())))}>
+                    <option value="" selected={template.status.is_none()}>{"Column it's created in"}</option>
+                    {STATUSES.iter().map(|status| {
+                        html! {
+                            <option
+                                value={status.value}
+                                selected={template.status.as_deref() == Some(status.value)}
+                            >{status.label}</option>
+                        }
+                    }).collect::<Html>()}
+                </select>
+            </div>
+            <div class="team-settings-row">
+                <span>{"Labels"}</span>
+                <LabelPicker
+                    selected={template.labels.clone()}
+                    onchange={{
+                        let template = template.clone();
+                        Callback::from(move |labels: Vec<String>| {
+                            template.set(Template {
+                                labels,
+                                ..(*template).clone()
+                            });
+                        })
+                    }}
+                />
+            </div>
+            <div class="team-settings-row team-settings-actions">
+                <button onclick={{
+                    let expanded = expanded.clone();
+                    Callback::from(move |_| {
+                        expanded.set(false);
+                    })
+                }}>{"Cancel"}</button>
+                <button onclick={save}>{"Save"}</button>
+            </div>
+        </div>
+    }
+}
+
+/// The template section of the team settings modal.
+#[function_component(TemplateSettings)]
+pub fn template_settings() -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+
+    html! {
+        <div class="team-settings-section">
+            <div class="team-settings-section-title">{"Templates"}</div>
+            {context.templates.iter().map(|template| {
+                html! {<TemplateEditor key={template.id.clone()} template={template.clone()} />}
+            }).collect::<Html>()}
+            <TemplateEditor />
+        </div>
+    }
+}
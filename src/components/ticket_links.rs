@@ -0,0 +1,228 @@
+use super::{card_edit_modal::status_label, ticket_search::TicketSearch};
+use crate::{common::ComponentProps, AppContext, OpenTicket, Ticket, TicketLink};
+use gloo_net::http::Request;
+use serde_json::json;
+use std::rc::Rc;
+use stylist::yew::styled_component;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{Event, HtmlSelectElement};
+use yew::{
+    classes, function_component, html, use_context, use_state, Callback, Html, Properties,
+    TargetCast,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct TicketLinksProps {
+    pub ticket: Rc<Ticket>,
+}
+
+pub struct LinkKind {
+    pub value: &'static str,
+    pub label: &'static str,
+}
+
+/// Link types as seen from the ticket that owns the link. The backend
+/// stores each link once and reports the inverse kind on the other ticket.
+pub const LINK_KINDS: &[LinkKind] = &[
+    LinkKind {
+        value: "blocks",
+        label: "Blocks",
+    },
+    LinkKind {
+        value: "blocked_by",
+        label: "Blocked by",
+    },
+    LinkKind {
+        value: "duplicates",
+        label: "Duplicates",
+    },
+    LinkKind {
+        value: "duplicated_by",
+        label: "Duplicated by",
+    },
+    LinkKind {
+        value: "relates_to",
+        label: "Relates to",
+    },
+];
+
+/// Links to tickets that are still open and block `ticket`.
+pub fn open_blockers(ticket: &Ticket) -> Vec<&TicketLink> {
+    ticket
+        .links
+        .iter()
+        .filter(|link| link.kind == "blocked_by" && !is_closed(&link.ticket.status))
+        .collect()
+}
+
+/// Whether a ticket in `status` needs no more work.
+pub fn is_closed(status: &str) -> bool {
+    matches!(status, "Complete" | "Rejected")
+}
+
+async fn create_link(ticket_id: String, kind: String, target_id: String) {
+    let _response = Request::post(&format!("/api/tickets/{}/links/", ticket_id))
+        .json(&json!({
+            "kind": kind,
+            "target_id": target_id,
+        }))
+        .unwrap()
+        .send()
+        .await;
+}
+
+async fn delete_link(id: String) {
+    let _response = Request::delete(&format!("/api/links/{}", id)).send().await;
+}
+
+#[styled_component(StyledTicketLinks)]
+fn styled_ticket_links(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            margin-top: 20px;
+            .ticket-links-title {
+                font-size: 16px;
+                color: #808080;
+            }
+            .ticket-link-kind {
+                margin-top: 8px;
+                font-size: 12px;
+                color: #808080;
+            }
+            .ticket-link {
+                display: flex;
+                align-items: center;
+                margin-top: 4px;
+            }
+            .ticket-link > a {
+                flex-grow: 1;
+                color: #1976d2;
+                cursor: pointer;
+            }
+            .ticket-link.closed > a {
+                text-decoration: line-through;
+            }
+            .ticket-link > span {
+                margin: 0 8px;
+                font-size: 12px;
+                color: #808080;
+            }
+            .ticket-links-add {
+                display: flex;
+                margin-top: 8px;
+            }
+            .ticket-links-add > select {
+                margin-right: 5px;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+#[function_component(TicketLinks)]
+pub fn ticket_links(props: &TicketLinksProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let kind = use_state(|| String::from(LINK_KINDS[0].value));
+
+    let add_link = {
+        let ticket_id = props.ticket.id.clone();
+        let kind = kind.clone();
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |target: Ticket| {
+            let ticket_id = ticket_id.clone();
+            let kind = (*kind).clone();
+            let update_tickets = update_tickets.clone();
+            spawn_local(async move {
+                create_link(ticket_id, kind, target.id).await;
+                update_tickets.emit(());
+            });
+        })
+    };
+    // Tickets already linked, and the ticket itself, can't be linked again.
+    let exclude = props
+        .ticket
+        .links
+        .iter()
+        .map(|link| link.ticket.id.clone())
+        .chain(std::iter::once(props.ticket.id.clone()))
+        .collect::<Vec<String>>();
+
+    html! {
+        <StyledTicketLinks>
+            <div class="ticket-links-title">{"Linked Tickets"}</div>
+            {LINK_KINDS.iter().map(|link_kind| {
+                let links = props
+                    .ticket
+                    .links
+                    .iter()
+                    .filter(|link| link.kind == link_kind.value)
+                    .collect::<Vec<&TicketLink>>();
+                if links.is_empty() {
+                    return html!();
+                }
+                html! {
+                    <>
+                        <div class="ticket-link-kind">{link_kind.label}</div>
+                        {links.into_iter().map(|link| {
+                            let open = {
+                                let id = link.ticket.id.clone();
+                                let update_open_ticket = context.update_open_ticket.clone();
+                                Callback::from(move |_| {
+                                    update_open_ticket.emit(Some(OpenTicket {
+                                        id: id.clone(),
+                                        field: None,
+                                    }));
+                                })
+                            };
+                            let remove = {
+                                let id = link.id.clone();
+                                let update_tickets = context.update_tickets.clone();
+                                Callback::from(move |_| {
+                                    let id = id.clone();
+                                    let update_tickets = update_tickets.clone();
+                                    spawn_local(async move {
+                                        delete_link(id).await;
+                                        update_tickets.emit(());
+                                    });
+                                })
+                            };
+                            html! {
+                                <div
+                                    class={classes!("ticket-link", is_closed(&link.ticket.status).then_some("closed"))}
+                                    key={link.id.clone()}
+                                >
+                                    <a onclick={open}>{link.ticket.name()}</a>
+                                    <span>{status_label(&link.ticket.status)}</span>
+                                    <button onclick={remove}>{"Remove"}</button>
+                                </div>
+                            }
+                        }).collect::<Html>()}
+                    </>
+                }
+            }).collect::<Html>()}
+            <div class="ticket-links-add">
+                <select
+                    onchange={{
+                        let kind = kind.clone();
+                        Callback::from(move |e: Event| {
+                            kind.set(e.target_dyn_into::<HtmlSelectElement>().unwrap().value());
+                        })
+                    }}
+                >
+                    {LINK_KINDS.iter().map(|link_kind| {
+                        html! {
+                            <option
+                                value={link_kind.value}
+                                selected={link_kind.value == *kind}
+                            >{link_kind.label}</option>
+                        }
+                    }).collect::<Html>()}
+                </select>
+                <TicketSearch
+                    onselect={add_link}
+                    exclude={exclude}
+                    placeholder={String::from("Link a ticket...")}
+                />
+            </div>
+        </StyledTicketLinks>
+    }
+}
@@ -0,0 +1,173 @@
+use super::card_edit_modal::status_label;
+use crate::{common::ComponentProps, AppContext, Ticket, TicketPage};
+use gloo_net::http::Request;
+use gloo_timers::callback::Timeout;
+use stylist::yew::styled_component;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, InputEvent};
+use yew::{
+    function_component, html, use_context, use_effect_with_deps, use_state, Callback, Html,
+    Properties, TargetCast,
+};
+
+/// Most results shown by the picker.
+const SEARCH_LIMIT: usize = 10;
+
+/// Pause in typing before tickets are searched.
+pub const SEARCH_DELAY_MS: u32 = 300;
+
+#[derive(Properties, PartialEq)]
+pub struct TicketSearchProps {
+    pub onselect: Callback<Ticket>,
+    /// Ids of tickets that can't be picked, such as the ticket being edited.
+    #[prop_or_default]
+    pub exclude: Vec<String>,
+    #[prop_or_default]
+    pub placeholder: Option<String>,
+}
+
+/// Tickets on `team` whose key, title or description matches `query`.
+pub async fn search_tickets(team: String, query: String) -> Vec<Ticket> {
+    let response = Request::get("/api/tickets/")
+        .query([
+            ("team", team.as_str()),
+            ("q", query.as_str()),
+            ("limit", &SEARCH_LIMIT.to_string()),
+        ])
+        .send()
+        .await;
+    match response {
+        Ok(r) => r
+            .json::<TicketPage>()
+            .await
+            .map(|page| page.tickets)
+            .unwrap_or_default(),
+        Err(_) => vec![],
+    }
+}
+
+#[styled_component(StyledTicketSearch)]
+fn styled_ticket_search(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            position: relative;
+            flex-grow: 1;
+            input {
+                width: 100%;
+                box-sizing: border-box;
+            }
+            .ticket-search-results {
+                position: absolute;
+                left: 0;
+                right: 0;
+                z-index: 1;
+                max-height: 200px;
+                overflow-y: auto;
+                background-color: #fff;
+                border-radius: 3px;
+                box-shadow: 0 10px 15px -3px rgb(0 0 0 / 0.1),
+                  0 4px 6px -4px rgb(0 0 0 / 0.1);
+            }
+            .ticket-search-result {
+                display: flex;
+                justify-content: space-between;
+                padding: 6px 8px;
+                cursor: pointer;
+            }
+            .ticket-search-result:hover {
+                background-color: #f0f0f0;
+            }
+            .ticket-search-result > span:last-child {
+                margin-left: 8px;
+                font-size: 12px;
+                color: #808080;
+                white-space: nowrap;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+/// A search-as-you-type picker over the current team's tickets.
+#[function_component(TicketSearch)]
+pub fn ticket_search(props: &TicketSearchProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let query = use_state(|| String::from(""));
+    // Results are kept with the query they answer so a slow response for an
+    // older query is never shown against newer input.
+    let results = use_state(|| (String::from(""), Vec::<Ticket>::new()));
+
+    // Each keystroke restarts the timer; dropping the timeout cancels it.
+    {
+        let results = results.clone();
+        let team = context.current_team.clone().unwrap_or_default();
+        use_effect_with_deps(
+            move |query| {
+                let query = (**query).clone();
+                let timeout = (!query.trim().is_empty()).then(|| {
+                    Timeout::new(SEARCH_DELAY_MS, move || {
+                        spawn_local(async move {
+                            let tickets = search_tickets(team, query.trim().to_string()).await;
+                            results.set((query, tickets));
+                        });
+                    })
+                });
+                move || drop(timeout)
+            },
+            query.clone(),
+        );
+    }
+
+    let oninput = {
+        let query = query.clone();
+        Callback::from(move |e: InputEvent| {
+            query.set(e.target_dyn_into::<HtmlInputElement>().unwrap().value());
+        })
+    };
+
+    let matches = if results.0 == *query && !query.trim().is_empty() {
+        results
+            .1
+            .iter()
+            .filter(|ticket| !props.exclude.contains(&ticket.id))
+            .cloned()
+            .collect::<Vec<Ticket>>()
+    } else {
+        vec![]
+    };
+
+    html! {
+        <StyledTicketSearch>
+            <input
+                type="text"
+                placeholder={props.placeholder.clone().unwrap_or(String::from("Search tickets"))}
+                value={(*query).clone()}
+                {oninput}
+            />
+            {if matches.is_empty() {
+                html!()
+            } else {
+                html! {
+                    <div class="ticket-search-results">
+                        {matches.into_iter().map(|ticket| {
+                            let onclick = {
+                                let query = query.clone();
+                                let onselect = props.onselect.clone();
+                                let ticket = ticket.clone();
+                                Callback::from(move |_| {
+                                    query.set(String::from(""));
+                                    onselect.emit(ticket.clone());
+                                })
+                            };
+                            html! {
+                                <div class="ticket-search-result" key={ticket.id.clone()} {onclick}>
+                                    <span>{ticket.name()}</span>
+                                    <span>{status_label(&ticket.status)}</span>
+                                </div>
+                            }
+                        }).collect::<Html>()}
+                    </div>
+                }
+            }}
+        </StyledTicketSearch>
+    }
+}
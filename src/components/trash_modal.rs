@@ -0,0 +1,205 @@
+use super::modal::Modal;
+use crate::{common::ComponentProps, time::local_datetime, AppContext, Ticket};
+use gloo_net::http::Request;
+use stylist::yew::styled_component;
+use wasm_bindgen_futures::spawn_local;
+use yew::{
+    function_component, html, use_context, use_effect_with_deps, use_state, Callback, Html,
+    Properties,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct TrashModalProps {
+    pub close: Callback<()>,
+}
+
+/// Deleted tickets of a team, most recently deleted first.
+async fn fetch_trash(team_id: String) -> Vec<Ticket> {
+    if team_id.is_empty() {
+        return vec![];
+    }
+    let response = Request::get(&format!("/api/teams/{}/trash/", team_id))
+        .send()
+        .await;
+    match response {
+        Ok(r) => r.json::<Vec<Ticket>>().await.unwrap_or_default(),
+        Err(_) => vec![],
+    }
+}
+
+/// Takes a ticket out of the trash and puts it back on its board.
+pub async fn restore_ticket(id: String) {
+    let _response = Request::post(&format!("/api/tickets/{}/restore/", id))
+        .send()
+        .await;
+}
+
+/// Deletes a trashed ticket for good. Only admins are allowed to.
+async fn purge_ticket(id: String) {
+    let _response = Request::delete(&format!("/api/tickets/{}/purge/", id))
+        .send()
+        .await;
+}
+
+#[styled_component(StyledTrashModal)]
+fn styled_trash_modal(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            display: flex;
+            flex-direction: column;
+            width: 100%;
+            padding: 16px;
+            font-family: sans-serif;
+            font-size: 14px;
+            color: #4c4e52;
+            .trash-title {
+                font-size: 22px;
+            }
+            .trash-empty {
+                margin-top: 20px;
+                color: #808080;
+            }
+            .trash-row {
+                display: flex;
+                align-items: center;
+                margin-top: 10px;
+            }
+            .trash-ticket {
+                display: flex;
+                flex-direction: column;
+                flex-grow: 1;
+                min-width: 0;
+            }
+            .trash-ticket > span:first-child {
+                overflow: hidden;
+                white-space: nowrap;
+                text-overflow: ellipsis;
+            }
+            .trash-ticket > span:last-child {
+                font-size: 12px;
+                color: #808080;
+            }
+            .trash-row > button {
+                margin-left: 5px;
+            }
+            .trash-row > button.danger {
+                background-color: #f44336;
+                color: #fff;
+                border-radius: 3px;
+                border: none;
+                padding: 4px 6px 3px;
+            }
+            button {
+                cursor: pointer;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+/// The current team's deleted tickets, with restore for everyone and
+/// permanent purging for admins.
+#[function_component(TrashModal)]
+pub fn trash_modal(props: &TrashModalProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let tickets = use_state(|| None::<Vec<Ticket>>);
+    let is_admin = context
+        .current_user
+        .as_ref()
+        .map(|user| user.is_admin)
+        .unwrap_or(false);
+
+    // Refetched on every board change so the undo toast and other tabs
+    // are reflected here.
+    {
+        let tickets = tickets.clone();
+        use_effect_with_deps(
+            move |(team_id, _)| {
+                let team_id = team_id.clone();
+                spawn_local(async move {
+                    tickets.set(Some(fetch_trash(team_id).await));
+                });
+                || ()
+            },
+            (context.current_team_id(), context.revision),
+        );
+    }
+
+    let restore = |id: String| {
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |_| {
+            let id = id.clone();
+            let update_tickets = update_tickets.clone();
+            spawn_local(async move {
+                restore_ticket(id).await;
+                update_tickets.emit(());
+            });
+        })
+    };
+    let purge = |ticket: &Ticket| {
+        let id = ticket.id.clone();
+        let message = format!(
+            "Permanently delete \"{}\"? This can't be undone.",
+            ticket.title
+        );
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |_| {
+            let confirmed = gloo_utils::window()
+                .confirm_with_message(&message)
+                .unwrap_or(false);
+            if !confirmed {
+                return;
+            }
+            let id = id.clone();
+            let update_tickets = update_tickets.clone();
+            spawn_local(async move {
+                purge_ticket(id).await;
+                update_tickets.emit(());
+            });
+        })
+    };
+
+    html! {
+        <Modal close={props.close.clone()}>
+            <StyledTrashModal>
+                <div class="trash-title">
+                    {format!("{} Trash", context.current_team.clone().unwrap_or_default())}
+                </div>
+                {match &*tickets {
+                    None => html!(<div class="trash-empty">{"Loading..."}</div>),
+                    Some(tickets) if tickets.is_empty() => {
+                        html!(<div class="trash-empty">{"The trash is empty."}</div>)
+                    }
+                    Some(tickets) => tickets.iter().map(|ticket| {
+                        let deleted = match (
+                            ticket.deleted_by.as_ref(),
+                            ticket.deleted_at.as_deref().and_then(local_datetime),
+                        ) {
+                            (Some(user), Some(at)) => format!("Deleted by {} on {}", user.name, at),
+                            (Some(user), None) => format!("Deleted by {}", user.name),
+                            (None, Some(at)) => format!("Deleted on {}", at),
+                            (None, None) => String::from("Deleted"),
+                        };
+                        html! {
+                            <div class="trash-row" key={ticket.id.clone()}>
+                                <div class="trash-ticket">
+                                    <span>{ticket.name()}</span>
+                                    <span>{deleted}</span>
+                                </div>
+                                <button onclick={restore(ticket.id.clone())}>{"Restore"}</button>
+                                {if is_admin {
+                                    html! {
+                                        <button class="danger" onclick={purge(ticket)}>
+                                            {"Delete forever"}
+                                        </button>
+                                    }
+                                } else {
+                                    html!()
+                                }}
+                            </div>
+                        }
+                    }).collect::<Html>(),
+                }}
+            </StyledTrashModal>
+        </Modal>
+    }
+}
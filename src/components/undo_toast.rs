@@ -0,0 +1,90 @@
+use super::trash_modal::restore_ticket;
+use crate::{common::ComponentProps, AppContext};
+use gloo_timers::callback::Timeout;
+use stylist::yew::styled_component;
+use wasm_bindgen_futures::spawn_local;
+use yew::{function_component, html, use_context, use_effect_with_deps, Callback};
+
+/// How long the undo toast stays up after a delete.
+const UNDO_MS: u32 = 6_000;
+
+#[styled_component(StyledUndoToast)]
+fn styled_undo_toast(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            position: fixed;
+            bottom: 24px;
+            left: 50%;
+            transform: translateX(-50%);
+            display: flex;
+            align-items: center;
+            gap: 16px;
+            padding: 10px 16px;
+            border-radius: 3px;
+            background-color: #323232;
+            box-shadow: 0 10px 15px -3px rgb(0 0 0 / 0.1),
+              0 4px 6px -4px rgb(0 0 0 / 0.1);
+            font-family: sans-serif;
+            font-size: 14px;
+            color: #fff;
+            button {
+                border: none;
+                background: none;
+                color: #74be43;
+                font-weight: bold;
+                cursor: pointer;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+/// Offers to restore the last deleted ticket for a few seconds.
+#[function_component(UndoToast)]
+pub fn undo_toast() -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+
+    // Each delete restarts the timer; dropping the timeout cancels it.
+    {
+        let update_deleted_ticket = context.update_deleted_ticket.clone();
+        use_effect_with_deps(
+            move |id| {
+                let timeout = id.as_ref().map(|_| {
+                    Timeout::new(UNDO_MS, move || {
+                        update_deleted_ticket.emit(None);
+                    })
+                });
+                move || drop(timeout)
+            },
+            context
+                .deleted_ticket
+                .as_ref()
+                .map(|ticket| ticket.id.clone()),
+        );
+    }
+
+    let ticket = match &context.deleted_ticket {
+        Some(ticket) => ticket.clone(),
+        None => return html!(),
+    };
+    let undo = {
+        let id = ticket.id.clone();
+        let update_tickets = context.update_tickets.clone();
+        let update_deleted_ticket = context.update_deleted_ticket.clone();
+        Callback::from(move |_| {
+            let id = id.clone();
+            let update_tickets = update_tickets.clone();
+            update_deleted_ticket.emit(None);
+            spawn_local(async move {
+                restore_ticket(id).await;
+                update_tickets.emit(());
+            });
+        })
+    };
+
+    html! {
+        <StyledUndoToast>
+            <span>{format!("Moved \"{}\" to the trash", ticket.title)}</span>
+            <button onclick={undo}>{"Undo"}</button>
+        </StyledUndoToast>
+    }
+}
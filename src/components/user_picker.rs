@@ -0,0 +1,116 @@
+use crate::{common::ComponentProps, AppContext, User};
+use stylist::{css, yew::styled_component};
+use yew::{classes, function_component, html, use_context, Callback, Html, Properties};
+
+const AVATAR_COLORS: &[&str] = &[
+    "#1976d2", "#388e3c", "#f57c00", "#7b1fa2", "#c2185b", "#0097a7", "#5d4037", "#455a64",
+];
+
+#[derive(Properties, PartialEq)]
+pub struct UserPickerProps {
+    /// Ids of the selected users.
+    pub selected: Vec<String>,
+    pub onchange: Callback<Vec<String>>,
+    /// What the picked users become, shown on their avatars.
+    pub role: String,
+}
+
+fn initials(name: &str) -> String {
+    name.split_whitespace()
+        .filter_map(|word| word.chars().next())
+        .take(2)
+        .collect::<String>()
+        .to_uppercase()
+}
+
+/// A stable colour per user, so an avatar looks the same on every card.
+fn avatar_color(user: &User) -> &'static str {
+    let hash = user.id.bytes().fold(0usize, |hash, b| {
+        hash.wrapping_mul(31).wrapping_add(b as usize)
+    });
+    AVATAR_COLORS[hash % AVATAR_COLORS.len()]
+}
+
+/// A round avatar with the user's initials; `role` is shown on hover.
+pub fn avatar(user: &User, role: &str) -> Html {
+    html! {
+        <span
+            class={classes!(css!("
+                display: inline-flex;
+                align-items: center;
+                justify-content: center;
+                width: 20px;
+                height: 20px;
+                border-radius: 50%;
+                color: #fff;
+                font-size: 9px;
+                font-weight: bold;
+            "), "avatar")}
+            title={format!("{} ({})", user.name, role)}
+            style={format!("background-color: {};", avatar_color(user))}
+        >{initials(&user.name)}</span>
+    }
+}
+
+#[styled_component(StyledUserPicker)]
+fn styled_user_picker(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            display: flex;
+            flex-wrap: wrap;
+            gap: 4px;
+            .user-option {
+                display: flex;
+                align-items: center;
+                gap: 4px;
+                padding: 2px 8px 2px 2px;
+                border: 1px solid #d3d3d3;
+                border-radius: 12px;
+                font-size: 12px;
+                cursor: pointer;
+                opacity: 0.5;
+            }
+            .user-option.selected {
+                border-color: #74be43;
+                opacity: 1;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+/// Toggles any number of users on or off.
+#[function_component(UserPicker)]
+pub fn user_picker(props: &UserPickerProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+
+    html! {
+        <StyledUserPicker>
+            {context.users.iter().map(|user| {
+                let selected = props.selected.contains(&user.id);
+                let toggle = {
+                    let id = user.id.clone();
+                    let current = props.selected.clone();
+                    let onchange = props.onchange.clone();
+                    Callback::from(move |_| {
+                        let mut updated = current.clone();
+                        if selected {
+                            updated.retain(|u| u != &id);
+                        } else {
+                            updated.push(id.clone());
+                        }
+                        onchange.emit(updated);
+                    })
+                };
+                html! {
+                    <span
+                        class={classes!("user-option", selected.then_some("selected"))}
+                        onclick={toggle}
+                    >
+                        {avatar(user, &props.role)}
+                        {user.name.clone()}
+                    </span>
+                }
+            }).collect::<Html>()}
+        </StyledUserPicker>
+    }
+}
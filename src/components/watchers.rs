@@ -0,0 +1,100 @@
+use super::user_picker::avatar;
+use crate::{common::ComponentProps, AppContext, Ticket};
+use gloo_net::http::Request;
+use std::rc::Rc;
+use stylist::yew::styled_component;
+use wasm_bindgen_futures::spawn_local;
+use yew::{function_component, html, use_context, Callback, Html, Properties};
+
+#[derive(Properties, PartialEq)]
+pub struct WatchersProps {
+    pub ticket: Rc<Ticket>,
+}
+
+/// Whether `user_id` follows `ticket`.
+pub fn is_watching(ticket: &Ticket, user_id: &str) -> bool {
+    ticket.watchers.iter().any(|watcher| watcher.id == user_id)
+}
+
+/// Watches or unwatches a ticket as the signed-in user.
+pub async fn set_watching(ticket_id: String, watching: bool) {
+    let url = format!("/api/tickets/{}/watch", ticket_id);
+    let request = if watching {
+        Request::put(&url)
+    } else {
+        Request::delete(&url)
+    };
+    let _response = request.send().await;
+}
+
+#[styled_component(StyledWatchers)]
+fn styled_watchers(props: &ComponentProps) -> Html {
+    html! {
+        <div class={css!("
+            display: flex;
+            align-items: center;
+            margin-top: 20px;
+            .watchers-label {
+                width: 20%;
+            }
+            .watchers-list {
+                display: flex;
+                flex-grow: 1;
+                flex-wrap: wrap;
+                gap: 8px;
+            }
+            .watcher {
+                display: flex;
+                align-items: center;
+                gap: 4px;
+            }
+        ")}>{props.children.clone()}</div>
+    }
+}
+
+/// The ticket's watcher list with a watch/unwatch toggle for the signed-in
+/// user. Reporters, owners and reviewers are added by the backend.
+#[function_component(Watchers)]
+pub fn watchers(props: &WatchersProps) -> Html {
+    let context = use_context::<AppContext>().expect("no ctx found");
+    let watching = match &context.current_user {
+        Some(user) => is_watching(&props.ticket, &user.id),
+        None => false,
+    };
+
+    let toggle = {
+        let ticket_id = props.ticket.id.clone();
+        let update_tickets = context.update_tickets.clone();
+        Callback::from(move |_| {
+            let ticket_id = ticket_id.clone();
+            let update_tickets = update_tickets.clone();
+            spawn_local(async move {
+                set_watching(ticket_id, !watching).await;
+                update_tickets.emit(());
+            });
+        })
+    };
+
+    html! {
+        <StyledWatchers>
+            <span class="watchers-label">{"Watchers: "}</span>
+            <div class="watchers-list">
+                {if props.ticket.watchers.is_empty() {
+                    html!(<span>{"No watchers"}</span>)
+                } else {
+                    props.ticket.watchers.iter().map(|watcher| {
+                        html! {
+                            <span class="watcher">
+                                {avatar(watcher, "watcher")}
+                                {watcher.name.clone()}
+                            </span>
+                        }
+                    }).collect::<Html>()
+                }}
+            </div>
+            <button disabled={context.current_user.is_none()} onclick={toggle}>
+                {if watching { "Unwatch" } else { "Watch" }}
+            </button>
+        </StyledWatchers>
+    }
+}
@@ -1,55 +1,591 @@
 mod common;
 mod components;
+mod markdown;
+mod time;
 
-use common::ComponentProps;
-use components::{header::Header, home::Home};
+use common::{url_param, ComponentProps};
+use components::{
+    board::DEFAULT_SORT, card_edit_modal::STATUSES, header::Header, home::Home,
+    undo_toast::UndoToast,
+};
 use gloo_net::http::Request;
+use gloo_storage::{LocalStorage, Storage};
 use serde::Deserialize;
-use std::vec;
+use serde_json::Value;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 use stylist::yew::styled_component;
 use wasm_bindgen_futures::spawn_local;
-use yew::{function_component, html, use_effect_with_deps, use_state, Callback, ContextProvider};
+use yew::{
+    function_component, html, use_effect_with_deps, use_mut_ref, use_reducer, use_state, Callback,
+    ContextProvider, Reducible,
+};
+
+/// Number of tickets requested per page of a status column.
+const PAGE_SIZE: usize = 50;
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
 pub struct User {
     pub id: String,
     pub name: String,
+    /// Admins can permanently purge deleted tickets.
+    #[serde(default)]
+    pub is_admin: bool,
 }
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
 pub struct Team {
     pub id: String,
     pub name: String,
+    /// Tickets can't be completed until their checklist is fully checked.
+    #[serde(default)]
+    pub require_checklist: bool,
+    /// Prefix of the team's ticket keys, e.g. "QE" for QE-142.
+    #[serde(default)]
+    pub key: String,
+    /// Accepted tickets are archived this many days after acceptance.
+    #[serde(default)]
+    pub auto_archive_days: Option<u32>,
+    /// Order of the ticket form rows, as built-in field keys and custom field
+    /// ids. Empty means the default order; see `custom_fields::form_fields`.
+    #[serde(default)]
+    pub layout: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Label {
+    pub id: String,
+    pub name: String,
+    pub color: String,
+}
+
+/// A team-defined ticket field. `kind` is one of the values in
+/// `custom_fields::FIELD_KINDS`; `options` only applies to selects.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct CustomField {
+    pub id: String,
+    pub name: String,
+    pub kind: String,
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+/// A team's starting point for a recurring kind of ticket. `title` may
+/// contain `{date}` and `{team}`, see `templates::expand_title`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Template {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Checklist item texts added to the new ticket.
+    #[serde(default)]
+    pub checklist: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Comment {
+    pub id: String,
+    pub body: String,
+    pub author: Option<User>,
+    /// The comment this one replies to, if any.
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ChecklistItem {
+    pub id: String,
+    pub text: String,
+    pub checked: bool,
+}
+
+/// A short reference to another ticket, such as the other end of a link
+/// or a sub-task.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct LinkedTicket {
+    pub id: String,
+    #[serde(default)]
+    pub key: Option<String>,
+    pub title: String,
+    pub status: String,
+}
+
+impl LinkedTicket {
+    /// The title, prefixed with the key if there is one.
+    pub fn name(&self) -> String {
+        match &self.key {
+            Some(key) => format!("{} {}", key, self.title),
+            None => self.title.clone(),
+        }
+    }
+}
+
+impl From<&Ticket> for LinkedTicket {
+    fn from(ticket: &Ticket) -> Self {
+        LinkedTicket {
+            id: ticket.id.clone(),
+            key: ticket.key.clone(),
+            title: ticket.title.clone(),
+            status: ticket.status.clone(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct TicketLink {
+    pub id: String,
+    /// How this ticket relates to `ticket`, e.g. "blocked_by".
+    pub kind: String,
+    pub ticket: LinkedTicket,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Attachment {
+    pub id: String,
+    pub filename: String,
+    pub content_type: String,
+    /// Size in bytes.
+    pub size: u64,
+    /// Where the file can be downloaded from.
+    pub url: String,
+    #[serde(default)]
+    pub thumbnail_url: Option<String>,
+    #[serde(default)]
+    pub uploaded_by: Option<User>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+}
+
+/// An audit event recorded by the backend for a ticket.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Activity {
+    pub id: String,
+    /// One of "created", "updated", "status_changed" or "deleted".
+    pub action: String,
+    pub actor: Option<User>,
+    /// The field an update changed, as sent by `update_ticket`.
+    #[serde(default)]
+    pub field: Option<String>,
+    #[serde(default)]
+    pub before: Option<Value>,
+    #[serde(default)]
+    pub after: Option<Value>,
+    #[serde(default)]
+    pub created_at: Option<String>,
 }
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
 pub struct Ticket {
     pub id: String,
+    /// Human-readable key such as "QE-142", made from the team's key prefix
+    /// and a number the backend assigns per team.
+    #[serde(default)]
+    pub key: Option<String>,
     pub title: String,
     pub description: String,
     pub status: String,
-    pub assigned_to: Option<User>,
+    /// Read through `owner()`, which falls back to `assigned_to`.
+    owner: Option<User>,
+    /// Earlier API versions called the owner `assigned_to`.
+    #[serde(default)]
+    assigned_to: Option<User>,
+    #[serde(default)]
+    pub reviewers: Vec<User>,
+    /// Users notified about changes to the ticket.
+    #[serde(default)]
+    pub watchers: Vec<User>,
+    #[serde(default)]
+    pub reporter: Option<User>,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    /// Custom field values keyed by field id.
+    #[serde(default)]
+    pub custom_fields: HashMap<String, Value>,
+    #[serde(default)]
+    pub checklist: Vec<ChecklistItem>,
+    #[serde(default)]
+    pub links: Vec<TicketLink>,
+    /// The epic this ticket is a sub-task of.
+    #[serde(default)]
+    pub parent: Option<LinkedTicket>,
+    #[serde(default)]
+    pub subtasks: Vec<LinkedTicket>,
+    #[serde(default)]
+    pub comment_count: usize,
+    #[serde(default)]
+    pub attachment_count: usize,
+    #[serde(default)]
+    pub due_date: Option<String>,
+    #[serde(default)]
+    pub priority: Option<String>,
+    #[serde(default)]
+    pub severity: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    /// Set once the ticket is archived and off the board.
+    #[serde(default)]
+    pub archived_at: Option<String>,
+    /// Set while the ticket is in its team's trash.
+    #[serde(default)]
+    pub deleted_at: Option<String>,
+    #[serde(default)]
+    pub deleted_by: Option<User>,
+}
+
+impl Ticket {
+    /// The owner, under either of the names the API has used for it.
+    pub fn owner(&self) -> Option<&User> {
+        self.owner.as_ref().or(self.assigned_to.as_ref())
+    }
+
+    /// Whether `reference` is this ticket's id or key. Keys are matched
+    /// case-insensitively so "qe-142" from a chat message still works.
+    pub fn is_referenced_by(&self, reference: &str) -> bool {
+        self.id == reference
+            || self
+                .key
+                .as_ref()
+                .is_some_and(|key| key.eq_ignore_ascii_case(reference))
+    }
+
+    /// The title, prefixed with the key if there is one.
+    pub fn name(&self) -> String {
+        match &self.key {
+            Some(key) => format!("{} {}", key, self.title),
+            None => self.title.clone(),
+        }
+    }
+
+    /// The key if the ticket has one, otherwise its id.
+    pub fn reference(&self) -> &str {
+        self.key.as_deref().unwrap_or(&self.id)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct TicketPage {
+    pub tickets: Vec<Ticket>,
+    pub next_cursor: Option<String>,
+    pub total: usize,
+}
+
+/// The loaded part of one status column. `total` is the server-side count,
+/// which can be larger than `tickets.len()` until every page is loaded.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TicketColumn {
+    pub tickets: Vec<Rc<Ticket>>,
+    pub next_cursor: Option<String>,
+    pub total: usize,
+    pub loading: bool,
+}
+
+impl TicketColumn {
+    fn from_page(page: TicketPage) -> Self {
+        TicketColumn {
+            tickets: page.tickets.into_iter().map(Rc::new).collect(),
+            next_cursor: page.next_cursor,
+            total: page.total,
+            loading: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TicketColumns {
+    pub columns: HashMap<String, TicketColumn>,
+    /// Bumped on every full refresh so views holding tickets fetched outside
+    /// the columns know to reload them.
+    pub revision: usize,
+    /// Request generation of the refresh currently shown.
+    generation: usize,
+}
+
+pub enum TicketColumnsAction {
+    Reset {
+        generation: usize,
+        columns: HashMap<String, TicketColumn>,
+    },
+    Loading(String),
+    Append {
+        status: String,
+        cursor: String,
+        page: Option<TicketPage>,
+    },
+}
+
+impl Reducible for TicketColumns {
+    type Action = TicketColumnsAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut columns = self.columns.clone();
+        let mut revision = self.revision;
+        let mut generation = self.generation;
+        match action {
+            TicketColumnsAction::Reset {
+                generation: requested,
+                columns: updated,
+            } => {
+                // Drop refreshes that finish after a newer one was started,
+                // e.g. for the previous team or filters.
+                if requested < self.generation {
+                    return self;
+                }
+                columns = updated;
+                revision += 1;
+                generation = requested;
+            }
+            TicketColumnsAction::Loading(status) => {
+                columns.entry(status).or_default().loading = true;
+            }
+            TicketColumnsAction::Append {
+                status,
+                cursor,
+                page,
+            } => {
+                let column = columns.entry(status).or_default();
+                column.loading = false;
+                // Drop pages for a cursor the column has already moved past,
+                // e.g. after a refresh or a duplicate scroll trigger.
+                if column.next_cursor.as_deref() != Some(cursor.as_str()) {
+                    return self;
+                }
+                if let Some(page) = page {
+                    column.tickets.extend(page.tickets.into_iter().map(Rc::new));
+                    column.next_cursor = page.next_cursor;
+                    column.total = page.total;
+                }
+            }
+        }
+        Rc::new(TicketColumns {
+            columns,
+            revision,
+            generation,
+        })
+    }
+}
+
+/// Board filters, sent with every column request so counts and pages only
+/// cover matching tickets.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TicketFilters {
+    pub priority: Option<String>,
+    pub severity: Option<String>,
+    pub label: Option<String>,
+    pub overdue: bool,
+    /// Only show sub-tasks of this ticket.
+    pub epic: Option<LinkedTicket>,
+}
+
+impl TicketFilters {
+    fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![];
+        if let Some(priority) = &self.priority {
+            query.push(("priority", priority.clone()));
+        }
+        if let Some(severity) = &self.severity {
+            query.push(("severity", severity.clone()));
+        }
+        if let Some(label) = &self.label {
+            query.push(("label", label.clone()));
+        }
+        if self.overdue {
+            query.push(("overdue", String::from("true")));
+        }
+        if let Some(epic) = &self.epic {
+            query.push(("parent", epic.id.clone()));
+        }
+        query
+    }
+}
+
+/// The ticket whose edit modal is open, and the field to start editing, if any.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenTicket {
+    pub id: String,
+    pub field: Option<String>,
+}
+
+/// A dialog about one ticket. It is kept here rather than in the card that
+/// opened it, since cards unmount when the board re-sorts or scrolls.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TicketDialog {
+    Duplicate(Rc<Ticket>),
+    MoveTeam(Rc<Ticket>),
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct AppContext {
+    pub current_user: Option<User>,
     pub users: Vec<User>,
     pub teams: Vec<Team>,
-    pub tickets: Vec<Ticket>,
+    pub labels: Vec<Label>,
+    pub fields: Vec<CustomField>,
+    pub templates: Vec<Template>,
+    pub columns: HashMap<String, TicketColumn>,
+    pub revision: usize,
     pub current_team: Option<String>,
+    pub sorts: HashMap<String, String>,
+    pub filters: TicketFilters,
+    pub open_ticket: Option<OpenTicket>,
+    /// The last ticket moved to the trash, while it can still be undone.
+    pub deleted_ticket: Option<Rc<Ticket>>,
+    pub ticket_dialog: Option<TicketDialog>,
     pub update_current_team: Callback<String>,
     pub update_tickets: Callback<()>,
+    pub update_sort: Callback<(String, String)>,
+    pub update_filters: Callback<TicketFilters>,
+    pub update_labels: Callback<()>,
+    pub update_fields: Callback<()>,
+    pub update_templates: Callback<()>,
+    pub update_teams: Callback<()>,
+    pub load_more: Callback<String>,
+    pub update_open_ticket: Callback<Option<OpenTicket>>,
+    pub update_deleted_ticket: Callback<Option<Rc<Ticket>>>,
+    pub update_ticket_dialog: Callback<Option<TicketDialog>>,
+}
+
+impl AppContext {
+    /// Id of the team selected in the header, or an empty string.
+    pub fn current_team_id(&self) -> String {
+        team_id(&self.teams, &self.current_team)
+    }
+
+    /// The team selected in the header, if it exists.
+    pub fn team(&self) -> Option<&Team> {
+        self.current_team
+            .as_ref()
+            .and_then(|name| self.teams.iter().find(|t| &t.name == name))
+    }
 }
 
-async fn refresh_tickets(team: String) -> Vec<Ticket> {
-    let mut tickets = vec![];
-    let response = Request::get(&format!("/api/tickets/?team={}", team))
+fn team_id(teams: &[Team], team: &Option<String>) -> String {
+    team.as_ref()
+        .and_then(|name| teams.iter().find(|t| &t.name == name))
+        .map(|t| t.id.clone())
+        .unwrap_or_default()
+}
+
+pub async fn fetch_labels(team_id: String) -> Vec<Label> {
+    if team_id.is_empty() {
+        return vec![];
+    }
+    let response = Request::get(&format!("/api/teams/{}/labels/", team_id))
         .send()
         .await;
-    if let Ok(r) = response {
-        tickets = r.json::<Vec<Ticket>>().await.unwrap_or(tickets);
+    match response {
+        Ok(r) => r.json::<Vec<Label>>().await.unwrap_or_default(),
+        Err(_) => vec![],
     }
-    tickets
+}
+
+pub async fn fetch_fields(team_id: String) -> Vec<CustomField> {
+    if team_id.is_empty() {
+        return vec![];
+    }
+    let response = Request::get(&format!("/api/teams/{}/fields/", team_id))
+        .send()
+        .await;
+    match response {
+        Ok(r) => r.json::<Vec<CustomField>>().await.unwrap_or_default(),
+        Err(_) => vec![],
+    }
+}
+
+async fn fetch_templates(team_id: String) -> Vec<Template> {
+    if team_id.is_empty() {
+        return vec![];
+    }
+    let response = Request::get(&format!("/api/teams/{}/templates/", team_id))
+        .send()
+        .await;
+    match response {
+        Ok(r) => r.json::<Vec<Template>>().await.unwrap_or_default(),
+        Err(_) => vec![],
+    }
+}
+
+async fn fetch_tickets(
+    team: &str,
+    status: &str,
+    sort: &str,
+    filters: &TicketFilters,
+    cursor: Option<&str>,
+    limit: usize,
+) -> Option<TicketPage> {
+    let mut request = Request::get("/api/tickets/")
+        .query([
+            ("team", team),
+            ("status", status),
+            ("sort", sort),
+            ("limit", &limit.to_string()),
+        ])
+        .query(filters.query());
+    if let Some(cursor) = cursor {
+        request = request.query([("cursor", cursor)]);
+    }
+    match request.send().await {
+        Ok(r) => r.json::<TicketPage>().await.ok(),
+        Err(_) => None,
+    }
+}
+
+/// Reloads the first page of every status column. Columns that already had
+/// more than a page loaded are refetched at their current length so a refresh
+/// doesn't collapse them back to the first page.
+async fn refresh_tickets(
+    team: String,
+    sorts: HashMap<String, String>,
+    filters: TicketFilters,
+    loaded: HashMap<String, usize>,
+) -> HashMap<String, TicketColumn> {
+    let mut columns = HashMap::new();
+    for status in STATUSES {
+        let sort = sorts
+            .get(status.value)
+            .map(|s| s.as_str())
+            .unwrap_or(DEFAULT_SORT);
+        let limit = loaded
+            .get(status.value)
+            .copied()
+            .unwrap_or(0)
+            .max(PAGE_SIZE);
+        let column = match fetch_tickets(&team, status.value, sort, &filters, None, limit).await {
+            Some(page) => TicketColumn::from_page(page),
+            None => TicketColumn::default(),
+        };
+        columns.insert(status.value.to_string(), column);
+    }
+    columns
+}
+
+/// Hands out increasing generations for board refreshes, so the columns
+/// reducer can tell which result is the latest.
+fn next_generation(counter: &RefCell<usize>) -> usize {
+    let mut counter = counter.borrow_mut();
+    *counter += 1;
+    *counter
+}
+
+fn sorts_key(team: &str) -> String {
+    format!("qe-mgr:sorts:{}", team)
+}
+
+fn load_sorts(team: &str) -> HashMap<String, String> {
+    LocalStorage::get(sorts_key(team)).unwrap_or_default()
 }
 
 #[styled_component(StyledMain)]
@@ -66,17 +602,32 @@ fn styled_app(props: &ComponentProps) -> Html {
 
 #[function_component(App)]
 fn app() -> Html {
+    let current_user = use_state(|| None::<User>);
     let users = use_state(|| Vec::<User>::new());
     let teams = use_state(|| Vec::<Team>::new());
-    let tickets = use_state(|| Vec::<Ticket>::new());
-    let current_team = use_state(|| None::<String>);
+    let labels = use_state(Vec::<Label>::new);
+    let fields = use_state(Vec::<CustomField>::new);
+    let templates = use_state(Vec::<Template>::new);
+    let columns = use_reducer(TicketColumns::default);
+    let generations = use_mut_ref(|| 0_usize);
+    let current_team = use_state(|| url_param("team"));
+    let sorts = use_state(HashMap::<String, String>::new);
+    let filters = use_state(TicketFilters::default);
+    let open_ticket = use_state(|| url_param("ticket").map(|id| OpenTicket { id, field: None }));
+    let deleted_ticket = use_state(|| None::<Rc<Ticket>>);
+    let ticket_dialog = use_state(|| None::<TicketDialog>);
 
     {
+        let current_user = current_user.clone();
         let users = users.clone();
         let teams = teams.clone();
         use_effect_with_deps(
             move |_| {
                 spawn_local(async move {
+                    let response = Request::get("/api/users/me").send().await;
+                    if let Ok(r) = response {
+                        current_user.set(r.json::<User>().await.ok());
+                    }
                     let response = Request::get("/api/users/").send().await;
                     if let Ok(r) = response {
                         users.set(r.json::<Vec<User>>().await.unwrap_or((*users).clone()));
@@ -92,49 +643,258 @@ fn app() -> Html {
         );
     }
     {
-        let tickets = tickets.clone();
+        let columns = columns.clone();
+        let generations = generations.clone();
+        let sorts = sorts.clone();
+        let filters = filters.clone();
         let current_team = current_team.clone();
         use_effect_with_deps(
             move |team| {
-                let team = team.clone();
+                let team = (**team).clone().unwrap_or("".into());
+                let team_sorts = load_sorts(&team);
+                sorts.set(team_sorts.clone());
+                filters.set(TicketFilters::default());
+                let generation = next_generation(&generations);
                 spawn_local(async move {
-                    tickets.set(refresh_tickets((*team).clone().unwrap_or("".into())).await);
+                    let updated =
+                        refresh_tickets(team, team_sorts, TicketFilters::default(), HashMap::new())
+                            .await;
+                    columns.dispatch(TicketColumnsAction::Reset {
+                        generation,
+                        columns: updated,
+                    });
                 });
                 || ()
             },
             current_team.clone(),
         );
     }
+    {
+        let labels = labels.clone();
+        let fields = fields.clone();
+        let templates = templates.clone();
+        use_effect_with_deps(
+            move |(teams, team)| {
+                let team_id = team_id(teams, team);
+                spawn_local(async move {
+                    labels.set(fetch_labels(team_id.clone()).await);
+                    fields.set(fetch_fields(team_id.clone()).await);
+                    templates.set(fetch_templates(team_id).await);
+                });
+                || ()
+            },
+            ((*teams).clone(), (*current_team).clone()),
+        );
+    }
+    let update_labels = {
+        let labels = labels.clone();
+        let teams = teams.clone();
+        let current_team = current_team.clone();
+        Callback::from(move |_| {
+            let labels = labels.clone();
+            let team_id = team_id(&teams, &current_team);
+            spawn_local(async move {
+                labels.set(fetch_labels(team_id).await);
+            });
+        })
+    };
+    let update_fields = {
+        let fields = fields.clone();
+        let teams = teams.clone();
+        let current_team = current_team.clone();
+        Callback::from(move |_| {
+            let fields = fields.clone();
+            let team_id = team_id(&teams, &current_team);
+            spawn_local(async move {
+                fields.set(fetch_fields(team_id).await);
+            });
+        })
+    };
+    let update_templates = {
+        let templates = templates.clone();
+        let teams = teams.clone();
+        let current_team = current_team.clone();
+        Callback::from(move |_| {
+            let templates = templates.clone();
+            let team_id = team_id(&teams, &current_team);
+            spawn_local(async move {
+                templates.set(fetch_templates(team_id).await);
+            });
+        })
+    };
+    let update_teams = {
+        let teams = teams.clone();
+        Callback::from(move |_| {
+            let teams = teams.clone();
+            spawn_local(async move {
+                let response = Request::get("/api/teams/").send().await;
+                if let Ok(r) = response {
+                    teams.set(r.json::<Vec<Team>>().await.unwrap_or((*teams).clone()));
+                }
+            });
+        })
+    };
     let update_tickets = {
-        let tickets = tickets.clone();
+        let columns = columns.clone();
+        let generations = generations.clone();
+        let sorts = sorts.clone();
+        let filters = filters.clone();
         let current_team = current_team.clone();
         Callback::from(move |_| {
-            let tickets = tickets.clone();
-            let current_team = current_team.clone();
+            let columns = columns.clone();
+            let team = (*current_team).clone().unwrap_or("".into());
+            let sorts = (*sorts).clone();
+            let filters = (*filters).clone();
+            let loaded = columns
+                .columns
+                .iter()
+                .map(|(status, column)| (status.clone(), column.tickets.len()))
+                .collect();
+            let generation = next_generation(&generations);
             spawn_local(async move {
-                tickets.set(refresh_tickets((*current_team).clone().unwrap_or("".into())).await);
+                let updated = refresh_tickets(team, sorts, filters, loaded).await;
+                columns.dispatch(TicketColumnsAction::Reset {
+                    generation,
+                    columns: updated,
+                });
+            });
+        })
+    };
+    let load_more = {
+        let columns = columns.clone();
+        let sorts = sorts.clone();
+        let filters = filters.clone();
+        let current_team = current_team.clone();
+        Callback::from(move |status: String| {
+            let column = columns.columns.get(&status).cloned().unwrap_or_default();
+            let cursor = match column.next_cursor {
+                Some(cursor) if !column.loading => cursor,
+                _ => return,
+            };
+            let columns = columns.clone();
+            let team = (*current_team).clone().unwrap_or("".into());
+            let sort = sorts.get(&status).cloned().unwrap_or(DEFAULT_SORT.into());
+            let filters = (*filters).clone();
+            columns.dispatch(TicketColumnsAction::Loading(status.clone()));
+            spawn_local(async move {
+                let page =
+                    fetch_tickets(&team, &status, &sort, &filters, Some(&cursor), PAGE_SIZE).await;
+                columns.dispatch(TicketColumnsAction::Append {
+                    status,
+                    cursor,
+                    page,
+                });
             });
         })
     };
     let update_current_team = {
         let current_team = current_team.clone();
+        let open_ticket = open_ticket.clone();
+        let ticket_dialog = ticket_dialog.clone();
         Callback::from(move |team| {
+            open_ticket.set(None);
+            ticket_dialog.set(None);
             current_team.set(Some(team));
         })
     };
+    let update_open_ticket = {
+        let open_ticket = open_ticket.clone();
+        Callback::from(move |ticket| {
+            open_ticket.set(ticket);
+        })
+    };
+    let update_deleted_ticket = {
+        let deleted_ticket = deleted_ticket.clone();
+        Callback::from(move |ticket| {
+            deleted_ticket.set(ticket);
+        })
+    };
+    let update_ticket_dialog = {
+        let ticket_dialog = ticket_dialog.clone();
+        Callback::from(move |dialog| {
+            ticket_dialog.set(dialog);
+        })
+    };
+
+    let update_sort = {
+        let columns = columns.clone();
+        let generations = generations.clone();
+        let sorts = sorts.clone();
+        let filters = filters.clone();
+        let current_team = current_team.clone();
+        Callback::from(move |(status, sort): (String, String)| {
+            let mut updated_sorts = (*sorts).clone();
+            updated_sorts.insert(status, sort);
+            let team = (*current_team).clone().unwrap_or("".into());
+            let _ = LocalStorage::set(sorts_key(&team), &updated_sorts);
+            sorts.set(updated_sorts.clone());
+            let columns = columns.clone();
+            let filters = (*filters).clone();
+            let generation = next_generation(&generations);
+            spawn_local(async move {
+                let updated = refresh_tickets(team, updated_sorts, filters, HashMap::new()).await;
+                columns.dispatch(TicketColumnsAction::Reset {
+                    generation,
+                    columns: updated,
+                });
+            });
+        })
+    };
+    let update_filters = {
+        let columns = columns.clone();
+        let generations = generations.clone();
+        let sorts = sorts.clone();
+        let filters = filters.clone();
+        let current_team = current_team.clone();
+        Callback::from(move |updated_filters: TicketFilters| {
+            let columns = columns.clone();
+            let team = (*current_team).clone().unwrap_or("".into());
+            let sorts = (*sorts).clone();
+            filters.set(updated_filters.clone());
+            let generation = next_generation(&generations);
+            spawn_local(async move {
+                let updated = refresh_tickets(team, sorts, updated_filters, HashMap::new()).await;
+                columns.dispatch(TicketColumnsAction::Reset {
+                    generation,
+                    columns: updated,
+                });
+            });
+        })
+    };
 
     html! {
         <ContextProvider<AppContext> context={AppContext{
+            current_user: (*current_user).clone(),
             users: (*users).clone(),
             teams: (*teams).clone(),
-            tickets: (*tickets).clone(),
+            labels: (*labels).clone(),
+            fields: (*fields).clone(),
+            templates: (*templates).clone(),
+            columns: columns.columns.clone(),
+            revision: columns.revision,
             current_team: (*current_team).clone(),
+            sorts: (*sorts).clone(),
+            filters: (*filters).clone(),
+            open_ticket: (*open_ticket).clone(),
+            deleted_ticket: (*deleted_ticket).clone(),
+            ticket_dialog: (*ticket_dialog).clone(),
             update_tickets,
             update_current_team,
+            update_sort,
+            update_filters,
+            update_labels,
+            update_fields,
+            update_templates,
+            update_teams,
+            load_more,
+            update_open_ticket,
+            update_deleted_ticket,
+            update_ticket_dialog,
         }}>
             <StyledMain>
                 <Header />
                 <Home />
+                <UndoToast />
             </StyledMain>
         </ContextProvider<AppContext>>
     }
@@ -143,3 +903,73 @@ fn app() -> Html {
 fn main() {
     yew::start_app::<App>();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn page(titles: &[&str], next_cursor: Option<&str>) -> TicketPage {
+        TicketPage {
+            tickets: titles
+                .iter()
+                .map(|title| {
+                    serde_json::from_value(json!({
+                        "id": title,
+                        "title": title,
+                        "description": "",
+                        "status": "Open",
+                    }))
+                    .unwrap()
+                })
+                .collect(),
+            next_cursor: next_cursor.map(String::from),
+            total: 10,
+        }
+    }
+
+    fn reset(generation: usize, titles: &[&str], next_cursor: Option<&str>) -> TicketColumnsAction {
+        let column = TicketColumn::from_page(page(titles, next_cursor));
+        TicketColumnsAction::Reset {
+            generation,
+            columns: HashMap::from([(String::from("Open"), column)]),
+        }
+    }
+
+    fn titles(columns: &TicketColumns) -> Vec<&str> {
+        columns.columns["Open"]
+            .tickets
+            .iter()
+            .map(|t| t.title.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn drops_refreshes_older_than_the_one_shown() {
+        let columns = Rc::new(TicketColumns::default())
+            .reduce(reset(2, &["new"], None))
+            .reduce(reset(1, &["old"], None));
+        assert_eq!(titles(&columns), ["new"]);
+
+        let columns = columns.reduce(reset(3, &["newer"], None));
+        assert_eq!(titles(&columns), ["newer"]);
+    }
+
+    #[test]
+    fn drops_pages_for_a_stale_cursor() {
+        let columns = Rc::new(TicketColumns::default())
+            .reduce(reset(1, &["a"], Some("c1")))
+            .reduce(TicketColumnsAction::Append {
+                status: String::from("Open"),
+                cursor: String::from("c1"),
+                page: Some(page(&["b"], Some("c2"))),
+            })
+            .reduce(TicketColumnsAction::Append {
+                status: String::from("Open"),
+                cursor: String::from("c1"),
+                page: Some(page(&["b"], Some("c2"))),
+            });
+        assert_eq!(titles(&columns), ["a", "b"]);
+        assert_eq!(columns.columns["Open"].next_cursor.as_deref(), Some("c2"));
+    }
+}
@@ -0,0 +1,107 @@
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+
+/// Link schemes that are safe to render; anything else is dropped.
+const SAFE_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Whether `url` is relative or uses one of `SAFE_SCHEMES`.
+pub fn is_safe_url(url: &str) -> bool {
+    // Browsers ignore whitespace and control characters inside a scheme,
+    // so "java\tscript:" must be caught as well.
+    let url = url
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_ascii_control())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    match url.find([':', '/', '?', '#']) {
+        Some(i) if url[i..].starts_with(':') => SAFE_SCHEMES.contains(&&url[..i]),
+        _ => true,
+    }
+}
+
+fn sanitize_url(url: CowStr) -> CowStr {
+    if is_safe_url(&url) {
+        url
+    } else {
+        CowStr::Borrowed("")
+    }
+}
+
+/// Renders Markdown to HTML that is safe to insert into the page.
+///
+/// Raw HTML in the source is shown as text and links or images with
+/// unsafe schemes such as `javascript:` lose their target.
+pub fn render_markdown(source: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+    let parser = Parser::new_ext(source, options).map(|event| match event {
+        Event::Html(html) => Event::Text(html),
+        Event::Start(Tag::Link(kind, url, title)) => {
+            Event::Start(Tag::Link(kind, sanitize_url(url), title))
+        }
+        Event::Start(Tag::Image(kind, url, title)) => {
+            Event::Start(Tag::Image(kind, sanitize_url(url), title))
+        }
+        event => event,
+    });
+    let mut output = String::new();
+    html::push_html(&mut output, parser);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allows_safe_and_relative_urls() {
+        assert!(is_safe_url("https://example.com"));
+        assert!(is_safe_url("mailto:qe@example.com"));
+        assert!(is_safe_url("/tickets/42"));
+        assert!(is_safe_url("../docs/setup.md"));
+        assert!(is_safe_url("#checklist"));
+        assert!(is_safe_url("?team=QE"));
+        assert!(is_safe_url("docs/a:b.md"));
+    }
+
+    #[test]
+    fn rejects_unsafe_schemes() {
+        assert!(!is_safe_url("javascript:alert(1)"));
+        assert!(!is_safe_url("JaVaScRiPt:alert(1)"));
+        assert!(!is_safe_url("data:text/html,<script>alert(1)</script>"));
+        assert!(!is_safe_url("vbscript:msgbox(1)"));
+    }
+
+    #[test]
+    fn rejects_obfuscated_schemes() {
+        assert!(!is_safe_url(" javascript:alert(1)"));
+        assert!(!is_safe_url("java\tscript:alert(1)"));
+        assert!(!is_safe_url("java\nscript:alert(1)"));
+        assert!(!is_safe_url("java\u{0}script:alert(1)"));
+        assert!(!is_safe_url("\u{1b}javascript:alert(1)"));
+    }
+
+    #[test]
+    fn drops_unsafe_link_targets() {
+        let output = render_markdown("[click](javascript:alert(1)) ![img](JAVASCRIPT:x)");
+        assert!(!output.to_ascii_lowercase().contains("javascript"));
+        assert!(output.contains("<a href=\"\">click</a>"));
+    }
+
+    #[test]
+    fn keeps_safe_link_targets() {
+        let output = render_markdown("[docs](https://example.com/docs) [ticket](/tickets/42)");
+        assert!(output.contains("href=\"https://example.com/docs\""));
+        assert!(output.contains("href=\"/tickets/42\""));
+    }
+
+    #[test]
+    fn escapes_raw_html() {
+        let output =
+            render_markdown("<script>alert(1)</script>\n\nhi <img src=x onerror=alert(1)>");
+        assert!(!output.contains("<script>"));
+        assert!(!output.contains("<img"));
+        assert!(output.contains("&lt;script&gt;"));
+    }
+}
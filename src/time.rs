@@ -0,0 +1,85 @@
+use js_sys::Date;
+use wasm_bindgen::JsValue;
+
+const DAY_MS: f64 = 86_400_000.0;
+
+/// Tickets due within this many days are highlighted as due soon.
+pub const DUE_SOON_DAYS: i64 = 2;
+
+/// Local midnight of a `YYYY-MM-DD` date, as produced by `<input type="date">`.
+fn local_date(date: &str) -> Option<Date> {
+    let mut parts = date.splitn(3, '-').map(|part| part.parse::<u32>().ok());
+    let (year, month, day) = (parts.next()??, parts.next()??, parts.next()??);
+    if month == 0 {
+        return None;
+    }
+    Some(Date::new_with_year_month_day(
+        year,
+        month as i32 - 1,
+        day as i32,
+    ))
+}
+
+fn today() -> Date {
+    let now = Date::new_0();
+    Date::new_with_year_month_day(
+        now.get_full_year(),
+        now.get_month() as i32,
+        now.get_date() as i32,
+    )
+}
+
+/// Today's local date as `YYYY-MM-DD`.
+pub fn today_iso() -> String {
+    let now = Date::new_0();
+    format!(
+        "{:04}-{:02}-{:02}",
+        now.get_full_year(),
+        now.get_month() + 1,
+        now.get_date()
+    )
+}
+
+/// Whole days from today until `date`; negative once it has passed.
+pub fn days_until(date: &str) -> Option<i64> {
+    let date = local_date(date)?;
+    // Rounded so a daylight saving change doesn't shift the count by a day.
+    Some(((date.get_time() - today().get_time()) / DAY_MS).round() as i64)
+}
+
+/// Short relative description of a due date and the colour to show it in.
+pub fn due_badge(date: &str) -> Option<(String, &'static str)> {
+    let days = days_until(date)?;
+    Some(match days {
+        d if d < 0 => (String::from("overdue"), "#f44336"),
+        0 => (String::from("due today"), "#ff9800"),
+        d if d <= DUE_SOON_DAYS => (format!("due in {}d", d), "#ff9800"),
+        d => (format!("due in {}d", d), "#808080"),
+    })
+}
+
+fn timestamp(value: &str) -> Option<Date> {
+    let date = Date::new(&value.into());
+    if date.get_time().is_nan() {
+        return None;
+    }
+    Some(date)
+}
+
+/// An ISO 8601 timestamp from the API, in the viewer's locale and timezone.
+pub fn local_datetime(value: &str) -> Option<String> {
+    let date = timestamp(value)?;
+    Some(date.to_locale_string("default", &JsValue::UNDEFINED).into())
+}
+
+/// How long ago a timestamp was, e.g. "5m", "3h" or "2d".
+pub fn age(value: &str) -> Option<String> {
+    let elapsed = (Date::now() - timestamp(value)?.get_time()).max(0.0);
+    let minutes = (elapsed / 60_000.0) as i64;
+    Some(match minutes {
+        0 => String::from("just now"),
+        m if m < 60 => format!("{}m", m),
+        m if m < 60 * 24 => format!("{}h", m / 60),
+        m => format!("{}d", m / (60 * 24)),
+    })
+}